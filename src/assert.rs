@@ -0,0 +1,97 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+use core::fmt;
+
+use crate::{ImageDimensions, SliceView};
+
+/// Compare a view against expected rows of pixels,
+/// panicking with the first mismatching position and values if they differ,
+/// for example `assert_view_eq!(view, &[[31, 41], [32, 42]])`
+#[macro_export]
+macro_rules! assert_view_eq {
+    ($view:expr, $expected:expr $(,)?) => {
+        if let Err(mismatch) = $crate::check_view_eq(&$view, $expected) {
+            panic!("assertion `view == expected` failed: {}", mismatch);
+        }
+    };
+}
+
+/// The first difference found by `check_view_eq`
+#[derive(Debug, PartialEq)]
+pub enum ViewMismatch<'e, T> {
+    Dimensions { actual: ImageDimensions, expected: ImageDimensions },
+    Pixel { row: usize, col: usize, actual: &'e T, expected: &'e T },
+}
+
+impl<'e, T: fmt::Debug> fmt::Display for ViewMismatch<'e, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ViewMismatch::Dimensions { actual, expected } => write!(
+                f,
+                "view is {}x{} but expected {}x{}",
+                actual.columns, actual.rows, expected.columns, expected.rows
+            ),
+            ViewMismatch::Pixel { row, col, actual, expected } => write!(
+                f,
+                "at (row {}, col {}) view has {:?} but expected {:?}",
+                row, col, actual, expected
+            ),
+        }
+    }
+}
+
+/// Compare a view against expected rows of pixels, as used by `assert_view_eq!`
+pub fn check_view_eq<'e, T: PartialEq, const C: usize>(
+    view: &SliceView<'e, T>,
+    expected: &'e [[T; C]],
+) -> Result<(), ViewMismatch<'e, T>> {
+    let expected_dims = ImageDimensions::new(C, expected.len());
+    if view.child_dims != expected_dims {
+        return Err(ViewMismatch::Dimensions { actual: view.child_dims, expected: expected_dims });
+    }
+    for (row, expected_row) in expected.iter().enumerate() {
+        for (col, expected_px) in expected_row.iter().enumerate() {
+            let actual = view.at(row, col);
+            if actual != expected_px {
+                return Err(ViewMismatch::Pixel { row, col, actual, expected: expected_px });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+
+    #[cfg(not(feature = "debug-checks"))]
+    #[test]
+    fn matching_view() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 0, 7, &FRAME_64, ImageDimensions::new(3, 3));
+        assert_view_eq!(view, &[[80, 11, 21], [81, 12, 22], [82, 13, 23]]);
+    }
+
+    #[test]
+    fn mismatched_view() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3, 2));
+        let expected = [[31, 41, 51], [32, 0, 52]];
+        assert_eq!(
+            check_view_eq(&view, &expected),
+            Err(ViewMismatch::Pixel { row: 1, col: 1, actual: &42, expected: &0 })
+        );
+        let err = std::panic::catch_unwind(|| assert_view_eq!(view, &expected)).unwrap_err();
+        let msg = err.downcast_ref::<String>().unwrap();
+        assert!(msg.contains("at (row 1, col 1) view has 42 but expected 0"), "{}", msg);
+
+        let short = [[31, 41, 51]];
+        assert_eq!(
+            check_view_eq(&view, &short).unwrap_err().to_string(),
+            "view is 3x2 but expected 3x1"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+use crate::{check_window, ImageDimensions, SliceView};
+
+/// Order of the pixels packed within each byte of a monochrome image
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BitOrder {
+    /// The leftmost pixel is the most significant bit
+    MsbFirst,
+    /// The leftmost pixel is the least significant bit
+    LsbFirst,
+}
+
+/// View a portion of a 1-bit-per-pixel image, packed into a byte slice.
+/// Each parent row starts on a byte boundary.
+#[derive(Copy, Clone, Debug)]
+pub struct BitSliceView<'a> {
+    pub parent_dims: ImageDimensions,
+    pub child_dims: ImageDimensions,
+    parent_start_col: usize,
+    parent_start_row: usize,
+    /// Number of bytes between the starts of adjacent parent rows
+    parent_stride: usize,
+    bit_order: BitOrder,
+    slice: &'a [u8],
+}
+
+impl<'a> BitSliceView<'a> {
+    /// View the `child_dims` window at `(parent_start_row, parent_start_col)` of the parent image.
+    /// Panics unless the window lies within the parent image and `slice` is long enough to back it,
+    /// since a window beyond the parent's width would read the padding at the end of each row.
+    pub fn new(parent_dims: ImageDimensions, parent_start_row: usize, parent_start_col: usize, slice: &'a [u8], child_dims: ImageDimensions, bit_order: BitOrder) -> Self {
+        let parent_stride = parent_dims.columns.div_ceil(8);
+        // checked in bits, each row of the parent starting on a byte boundary
+        assert!(
+            check_window(parent_dims, 8 * parent_stride, parent_start_row, parent_start_col, child_dims, 8 * slice.len()).is_ok(),
+            "view window does not fit within parent"
+        );
+        Self {
+            parent_dims,
+            child_dims,
+            parent_start_col,
+            parent_start_row,
+            parent_stride,
+            bit_order,
+            slice,
+        }
+    }
+
+    /// Simply wrap an existing packed image
+    pub fn new_passthru(parent_dims: ImageDimensions, slice: &'a [u8], bit_order: BitOrder) -> Self {
+        Self::new(parent_dims, 0, 0, slice, parent_dims, bit_order)
+    }
+
+    /// Number of bytes between the starts of adjacent parent rows
+    pub fn parent_stride(&self) -> usize {
+        self.parent_stride
+    }
+
+    /// A view of the `dims` window of this view with its top-left at local `(row, col)`.
+    /// Panics unless the window lies within the parent image, as for `new`.
+    pub fn sub_view(&self, row: usize, col: usize, dims: ImageDimensions) -> Self {
+        let (start_row, start_col) = (self.parent_start_row + row, self.parent_start_col + col);
+        Self::new(self.parent_dims, start_row, start_col, self.slice, dims, self.bit_order)
+    }
+
+    /// Whether the pixel at local `(row, col)` is set.
+    /// Panics if `(row, col)` lies outside the view.
+    pub fn get_bit(&self, row: usize, col: usize) -> bool {
+        assert!(
+            row < self.child_dims.rows && col < self.child_dims.columns,
+            "({}, {}) is outside view of {:?}", row, col, self.child_dims
+        );
+        let frame_x = self.parent_start_col + col;
+        let frame_y = self.parent_start_row + row;
+        let byte = self.slice[frame_y * self.parent_stride + frame_x / 8];
+        let bit = frame_x % 8;
+        let mask = match self.bit_order {
+            BitOrder::MsbFirst => 0x80 >> bit,
+            BitOrder::LsbFirst => 0x01 << bit,
+        };
+        byte & mask != 0
+    }
+}
+
+impl<'a> SliceView<'a, u8> {
+    /// Bit `plane` of each pixel, in row-major order:
+    /// for example `bit_plane(0)` yields the least significant bit plane of the view
+    pub fn bit_plane(&self, plane: u8) -> impl Iterator<Item = bool> + 'a {
+        assert!(plane < 8, "bit plane out of range");
+        self.iter().map(move |&px| px & (1 << plane) != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packed_pixels() {
+        // 12x2 image, with each row padded out to 2 bytes
+        let packed = [0b1000_0001u8, 0b1010_0000, 0b0100_0000, 0b0001_0000];
+        let parent = ImageDimensions::new(12, 2);
+
+        let msb = BitSliceView::new_passthru(parent, &packed, BitOrder::MsbFirst);
+        let set: Vec<(usize, usize)> = (0..2)
+            .flat_map(|row| (0..12).map(move |col| (row, col)))
+            .filter(|&(row, col)| msb.get_bit(row, col))
+            .collect();
+        assert_eq!(set, [(0, 0), (0, 7), (0, 8), (0, 10), (1, 1), (1, 11)]);
+
+        let lsb = BitSliceView::new_passthru(parent, &packed, BitOrder::LsbFirst);
+        assert!(lsb.get_bit(0, 0));
+        assert!(lsb.get_bit(0, 7));
+        assert!(!lsb.get_bit(0, 8));
+        assert!(lsb.get_bit(1, 6));
+        assert!(!lsb.get_bit(1, 1));
+
+        // a window starting partway through a byte
+        let window = BitSliceView::new(parent, 0, 7, &packed, ImageDimensions::new(4, 2), BitOrder::MsbFirst);
+        assert!(window.get_bit(0, 0));
+        assert!(window.get_bit(0, 1));
+        assert!(!window.get_bit(0, 2));
+        assert!(window.get_bit(0, 3));
+        assert!((0..4).all(|col| !window.get_bit(1, col)));
+    }
+
+    #[test]
+    #[should_panic(expected = "view window does not fit within parent")]
+    fn window_into_row_padding() {
+        // 12x2 image: a window of columns 10..14 would read the padding bits of row 0
+        let packed = [0xFFu8; 4];
+        let _ = BitSliceView::new(ImageDimensions::new(12, 2), 0, 10, &packed, ImageDimensions::new(4, 1), BitOrder::MsbFirst);
+    }
+
+    #[test]
+    #[should_panic(expected = "view window does not fit within parent")]
+    fn window_beyond_slice() {
+        let packed = [0xFFu8; 3];
+        let _ = BitSliceView::new_passthru(ImageDimensions::new(12, 2), &packed, BitOrder::MsbFirst);
+    }
+
+    #[test]
+    #[should_panic(expected = "is outside view")]
+    fn bit_beyond_window() {
+        // the bit is within the parent, but not the window
+        let packed = [0xFFu8; 4];
+        let window = BitSliceView::new(ImageDimensions::new(12, 2), 0, 2, &packed, ImageDimensions::new(4, 2), BitOrder::MsbFirst);
+        let _ = window.get_bit(0, 4);
+    }
+
+    #[test]
+    fn lsb_plane() {
+        let frame = [0b0000_0011u8, 0b1000_0000, 0b0000_0101, 0xFF, 0x02, 0x7F];
+        let view = SliceView::new(ImageDimensions::new(3, 2), 0, 1, &frame, ImageDimensions::new(2, 2));
+        // 0x80 0x05 / 0x02 0x7F
+        let lsb: Vec<bool> = view.bit_plane(0).collect();
+        assert_eq!(lsb, [false, true, false, true]);
+        assert!(lsb.iter().zip(view.iter()).all(|(&bit, &px)| bit == (px & 1 == 1)));
+        let msb: Vec<bool> = view.bit_plane(7).collect();
+        assert_eq!(msb, [true, false, false, false]);
+    }
+}
@@ -0,0 +1,53 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! Reinterpreting the element type of a view, with `bytemuck`
+
+use bytemuck::Pod;
+
+use crate::SliceView;
+
+impl<'a, T: Pod> SliceView<'a, T> {
+    /// The same view with each element reinterpreted as a `U` of the same size,
+    /// such as `[u8; 4]` channels for packed `u32` RGBA pixels.
+    /// The pixel grid is unchanged. `None` if `U` differs in size from `T`,
+    /// or the parent slice is not suitably aligned for `U`.
+    pub fn reinterpret_elements<U: Pod>(&self) -> Option<SliceView<'a, U>> {
+        if core::mem::size_of::<U>() != core::mem::size_of::<T>() {
+            return None;
+        }
+        let slice: &'a [U] = bytemuck::try_cast_slice(self.slice).ok()?;
+        Some(SliceView {
+            passthru: self.passthru,
+            parent_dims: self.parent_dims,
+            child_dims: self.child_dims,
+            parent_start_col: self.parent_start_col,
+            parent_start_row: self.parent_start_row,
+            parent_stride: self.parent_stride,
+            pixel_stride: self.pixel_stride,
+            transposed: self.transposed,
+            flip_window_rows: self.flip_window_rows,
+            flip_window_cols: self.flip_window_cols,
+            slice,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::FRAME_64_DIM;
+    use crate::{ImageDimensions, SliceView};
+
+    #[test]
+    fn packed_rgba_channels() {
+        let frame: Vec<u32> = (0..64u32).map(|i| u32::from_le_bytes([i as u8, 100, 200, 255])).collect();
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &frame, ImageDimensions::new(3, 2));
+        let channels = view.reinterpret_elements::<[u8; 4]>().unwrap();
+        assert_eq!(channels.child_dims, view.child_dims);
+        assert_eq!(channels[0], [10, 100, 200, 255]);
+        assert_eq!(channels[4][0], 19);
+        assert!(view.reinterpret_elements::<u16>().is_none());
+    }
+}
@@ -0,0 +1,155 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+use crate::{ImageDimensions, SliceView};
+
+/// Reflected CRC-32 (IEEE 802.3) polynomial
+const CRC32_POLY: u32 = 0xEDB8_8320;
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ CRC32_POLY } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+static CRC32_TABLE: [u32; 256] = crc32_table();
+
+/// Largest prime below 2^16, the Adler-32 modulus
+const ADLER_MOD: u32 = 65521;
+
+impl<'a> SliceView<'a, u8> {
+    /// CRC-32 (as used by zlib/PNG) of the viewed pixels in row-major order.
+    /// Only the viewed columns contribute, regardless of the parent stride.
+    pub fn crc32(&self) -> u32 {
+        let crc = self.iter().fold(!0u32, |crc, &px| {
+            CRC32_TABLE[((crc ^ px as u32) & 0xFF) as usize] ^ (crc >> 8)
+        });
+        !crc
+    }
+
+    /// Adler-32 (as used by zlib) of the viewed pixels in row-major order.
+    /// Cheaper than `crc32`, and likewise independent of the parent stride.
+    pub fn adler32(&self) -> u32 {
+        let (a, b) = self.iter().fold((1u32, 0u32), |(a, b), &px| {
+            let a = (a + px as u32) % ADLER_MOD;
+            (a, (b + a) % ADLER_MOD)
+        });
+        (b << 16) | a
+    }
+}
+
+/// Tracks an Adler-32 digest of each tile of successive frames,
+/// so that only the tiles which changed since the previous frame need be sent.
+/// The digests are kept in a caller-provided buffer.
+pub struct DigestView<'d> {
+    tile_dims: ImageDimensions,
+    digests: &'d mut [u32],
+    /// Number of leading entries of `digests` which hold a digest of a previous frame
+    known: usize,
+}
+
+impl<'d> DigestView<'d> {
+    /// Track tiles of `tile_dims`, storing their digests in `digests`,
+    /// which must hold at least `tile_count` entries for the frames to be compared.
+    pub fn new(tile_dims: ImageDimensions, digests: &'d mut [u32]) -> Self {
+        assert!(tile_dims.columns > 0 && tile_dims.rows > 0, "tile dimensions must be nonzero");
+        Self { tile_dims, digests, known: 0 }
+    }
+
+    /// Number of tiles of `tile_dims` covering a view of `dims`, including clipped edge tiles
+    pub fn tile_count(dims: ImageDimensions, tile_dims: ImageDimensions) -> usize {
+        crate::tile_count(dims, tile_dims)
+    }
+
+    /// Digest the tiles of `frame`, yielding the local `(start_row, start_col, dims)`
+    /// of each tile whose digest differs from the previous frame's.
+    /// Every tile is reported for the first frame.
+    /// Tiles are digested as the iterator advances: tiles not reached before it is dropped
+    /// keep the previous frame's digest, so they are still reported as changed next time.
+    /// Panics if the digest buffer is too small for the tiles of `frame`.
+    pub fn update<'s>(&'s mut self, frame: SliceView<'s, u8>) -> impl Iterator<Item = (usize, usize, ImageDimensions)> + 's {
+        let count = Self::tile_count(frame.child_dims, self.tile_dims);
+        assert!(count <= self.digests.len(), "digest buffer too small for {} tiles", count);
+        let digests: &'s mut [u32] = self.digests;
+        let known = &mut self.known;
+        frame.tile_coords(self.tile_dims).enumerate().filter_map(move |(idx, (row, col, dims))| {
+            let digest = frame.sub_view(row, col, dims).adler32();
+            let changed = idx >= *known || digests[idx] != digest;
+            digests[idx] = digest;
+            *known = (*known).max(idx + 1);
+            if changed { Some((row, col, dims)) } else { None }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+    use crate::{DigestView, ImageDimensions, SliceView};
+
+    #[test]
+    fn crc32_check_value() {
+        let data = *b"123456789";
+        let view = SliceView::new_passthru(ImageDimensions::new(3, 3), &data);
+        assert_eq!(view.crc32(), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn crc32_detects_change() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let child = ImageDimensions::new(3, 2);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, child);
+
+        // the same region copied into a padded buffer with a different origin
+        const STRIDE: usize = 5;
+        let mut padded = [0u8; STRIDE * 3];
+        for row in 0..2 {
+            for col in 0..3 {
+                padded[(row + 1) * STRIDE + col] = view[row * 3 + col];
+            }
+        }
+        let copy_parent = ImageDimensions::new(4, 3);
+        let copy = SliceView::new_with_stride(copy_parent, STRIDE, 1, 0, &padded, child);
+        assert_eq!(view.crc32(), copy.crc32());
+
+        padded[2 * STRIDE + 1] ^= 1;
+        let changed = SliceView::new_with_stride(copy_parent, STRIDE, 1, 0, &padded, child);
+        assert_ne!(view.crc32(), changed.crc32());
+    }
+
+    #[test]
+    fn adler32_check_value() {
+        let data = *b"Wikipedia";
+        let view = SliceView::new_passthru(ImageDimensions::new(9, 1), &data);
+        assert_eq!(view.adler32(), 0x11E6_0398);
+    }
+
+    #[test]
+    fn digest_reports_changed_tile() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let tile_dims = ImageDimensions::new(4, 4);
+        let mut digests = [0u32; 4];
+        let mut tracker = DigestView::new(tile_dims, &mut digests);
+
+        let first = SliceView::new_passthru(parent, &FRAME_64);
+        assert_eq!(tracker.update(first).count(), 4);
+        assert_eq!(tracker.update(first).count(), 0);
+
+        let mut frame = FRAME_64;
+        frame[5 * FRAME_64_DIM + 2] = 0;
+        let second = SliceView::new_passthru(parent, &frame);
+        let changed: Vec<_> = tracker.update(second).collect();
+        assert_eq!(changed, [(4, 0, tile_dims)]);
+    }
+}
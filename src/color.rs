@@ -0,0 +1,122 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! Typed color pixels, for viewing raw color framebuffers
+
+use crate::{byte_chunks, ImageDimensions, SliceView};
+
+/// A 16 bit color pixel, packed as 5 bits of red, 6 of green and 5 of blue,
+/// with red in the most significant bits
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgb565(pub u16);
+
+impl Rgb565 {
+    /// Pack the channels, which are truncated to their 5, 6 and 5 bit ranges
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self(((r as u16 & 0x1F) << 11) | ((g as u16 & 0x3F) << 5) | (b as u16 & 0x1F))
+    }
+
+    /// The red channel, in the range 0..=31
+    pub fn r(&self) -> u8 {
+        (self.0 >> 11) as u8
+    }
+
+    /// The green channel, in the range 0..=63
+    pub fn g(&self) -> u8 {
+        ((self.0 >> 5) & 0x3F) as u8
+    }
+
+    /// The blue channel, in the range 0..=31
+    pub fn b(&self) -> u8 {
+        (self.0 & 0x1F) as u8
+    }
+
+    /// Expand each channel to 8 bits, replicating its high bits into the low bits
+    /// so that full scale maps to 255
+    pub fn to_rgb888(&self) -> Rgb888 {
+        let (r, g, b) = (self.r(), self.g(), self.b());
+        Rgb888([(r << 3) | (r >> 2), (g << 2) | (g >> 4), (b << 3) | (b >> 2)])
+    }
+}
+
+/// A 24 bit color pixel, stored as red, green and blue bytes
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgb888(pub [u8; 3]);
+
+impl Rgb888 {
+    /// Store the channels as given
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self([r, g, b])
+    }
+
+    /// The red channel
+    pub fn r(&self) -> u8 {
+        self.0[0]
+    }
+
+    /// The green channel
+    pub fn g(&self) -> u8 {
+        self.0[1]
+    }
+
+    /// The blue channel
+    pub fn b(&self) -> u8 {
+        self.0[2]
+    }
+}
+
+impl<'a> SliceView<'a, Rgb565> {
+    /// View a raw framebuffer of native-endian 16 bit words as `Rgb565` pixels
+    pub fn from_u16_slice(parent_dims: ImageDimensions, slice: &'a [u16]) -> Self {
+        // Rgb565 is a transparent wrapper of u16, so shares its layout
+        let pixels = unsafe { core::slice::from_raw_parts(slice.as_ptr() as *const Rgb565, slice.len()) };
+        Self::new_passthru(parent_dims, pixels)
+    }
+}
+
+impl<'a> SliceView<'a, Rgb888> {
+    /// View a raw framebuffer of packed `[r, g, b, r, g, b, ...]` bytes as `Rgb888` pixels.
+    /// Trailing bytes that do not form a whole pixel are ignored.
+    pub fn from_u8_slice(parent_dims: ImageDimensions, slice: &'a [u8]) -> Self {
+        let triples = byte_chunks::<3>(slice);
+        // Rgb888 is a transparent wrapper of [u8; 3], so shares its layout
+        let pixels = unsafe { core::slice::from_raw_parts(triples.as_ptr() as *const Rgb888, triples.len()) };
+        Self::new_passthru(parent_dims, pixels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgb565_channels() {
+        let raw = [0xF800u16, 0x07E0, 0x001F, 0x8A52, 0xFFFF, 0x0000];
+        let view = SliceView::<Rgb565>::from_u16_slice(ImageDimensions::new(3, 2), &raw);
+        let channels = |px: &Rgb565| (px.r(), px.g(), px.b());
+        assert_eq!(channels(&view[0]), (31, 0, 0));
+        assert_eq!(channels(&view[1]), (0, 63, 0));
+        assert_eq!(channels(&view[2]), (0, 0, 31));
+        // 10001 010010 10010
+        assert_eq!(channels(&view[3]), (17, 18, 18));
+        assert_eq!(Rgb565::new(17, 18, 18), view[3]);
+        assert_eq!(view[4].to_rgb888(), Rgb888::new(255, 255, 255));
+        assert_eq!(view[5].to_rgb888(), Rgb888::new(0, 0, 0));
+        assert_eq!(view[0].to_rgb888(), Rgb888::new(255, 0, 0));
+    }
+
+    #[test]
+    fn rgb888_channels() {
+        let raw = [1u8, 2, 3, 10, 20, 30, 100, 200, 250, 7, 8, 9, 0xAA];
+        let parent = ImageDimensions::new(2, 2);
+        let view = SliceView::<Rgb888>::from_u8_slice(parent, &raw);
+        assert_eq!(view.len(), 4);
+        assert_eq!((view[1].r(), view[1].g(), view[1].b()), (10, 20, 30));
+        assert_eq!(view[2], Rgb888::new(100, 200, 250));
+        let sub = view.sub_view(1, 1, ImageDimensions::new(1, 1));
+        assert_eq!(sub[0].b(), 9);
+    }
+}
@@ -0,0 +1,203 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! Conversion of the pixels of a view into densely packed buffers
+
+use crate::{ImageDimensions, SliceView, SliceViewError};
+
+impl<'a, T: Copy + Into<f32>> SliceView<'a, T> {
+    /// Write `(px - mean) * scale` for each pixel, densely packed into `dst`,
+    /// as typically needed for the input of a neural network
+    pub fn to_normalized_f32(&self, mean: f32, scale: f32, dst: &mut [f32]) {
+        assert!(dst.len() >= self.len(), "dst too small for view");
+        for (out, &px) in dst.iter_mut().zip(self.iter()) {
+            *out = (px.into() - mean) * scale;
+        }
+    }
+
+    /// Write `(px - min) / (max - min)` for each pixel, densely packed into `dst`,
+    /// where `min` and `max` are the extremes of the view, so that the output spans
+    /// 0.0 to 1.0. A view of a single value is written as all zeros.
+    pub fn normalize_minmax(&self, dst: &mut [f32]) {
+        assert!(dst.len() >= self.len(), "dst too small for view");
+        let (min, max) = self.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &px| {
+            let val: f32 = px.into();
+            (min.min(val), max.max(val))
+        });
+        let range = max - min;
+        for (out, &px) in dst.iter_mut().zip(self.iter()) {
+            *out = if range > 0.0 { (px.into() - min) / range } else { 0.0 };
+        }
+    }
+}
+
+impl<'a> SliceView<'a, u16> {
+    /// Write each pixel shifted right by `shift` bits, densely packed into `dst`,
+    /// saturating at 255: for example `shift = 4` previews 12-bit data as 8-bit.
+    pub fn to_u8_scaled(&self, shift: u32, dst: &mut [u8]) {
+        assert!(dst.len() >= self.len(), "dst too small for view");
+        for (out, &px) in dst.iter_mut().zip(self.iter()) {
+            *out = px.checked_shr(shift).unwrap_or(0).min(u8::MAX as u16) as u8;
+        }
+    }
+}
+
+/// Pack three same-size single-channel views into `dst` as `[r, g, b, r, g, b, ...]`
+pub fn interleave3<T: Copy>(r: &SliceView<T>, g: &SliceView<T>, b: &SliceView<T>, dst: &mut [T]) {
+    assert!(
+        r.child_dims == g.child_dims && r.child_dims == b.child_dims,
+        "planes differ in dimensions"
+    );
+    assert!(dst.len() >= 3 * r.len(), "dst too small for planes");
+    let planes = r.iter().zip(g.iter()).zip(b.iter());
+    for (out, ((&r, &g), &b)) in dst.chunks_exact_mut(3).zip(planes) {
+        out.copy_from_slice(&[r, g, b]);
+    }
+}
+
+/// Apply `f` to each pair of corresponding pixels of two same-size views,
+/// writing the results densely packed into `dst`
+pub fn combine_into<T, U, O, F: FnMut(&T, &U) -> O>(a: &SliceView<T>, b: &SliceView<U>, mut f: F, dst: &mut [O]) {
+    assert_eq!(a.child_dims, b.child_dims, "views differ in dimensions");
+    assert!(dst.len() >= a.len(), "dst too small for views");
+    for (out, (a, b)) in dst.iter_mut().zip(a.iter().zip(b.iter())) {
+        *out = f(a, b);
+    }
+}
+
+/// Write the pixels of `top` followed by those of `bottom` densely packed into `dst`,
+/// returning the dimensions of the combined image.
+/// Fails with `DimensionMismatch` if the views differ in width.
+pub fn stack_vertical<T: Copy>(top: &SliceView<T>, bottom: &SliceView<T>, dst: &mut [T]) -> Result<ImageDimensions, SliceViewError> {
+    if top.child_dims.columns != bottom.child_dims.columns {
+        return Err(SliceViewError::DimensionMismatch);
+    }
+    let dims = ImageDimensions::new(top.child_dims.columns, top.child_dims.rows + bottom.child_dims.rows);
+    assert!(dst.len() >= dims.columns * dims.rows, "dst too small for views");
+    for (out, &px) in dst.iter_mut().zip(top.iter().chain(bottom.iter())) {
+        *out = px;
+    }
+    Ok(dims)
+}
+
+/// Write the pixels of `left` and `right` side by side densely packed into `dst`,
+/// returning the dimensions of the combined image.
+/// Fails with `DimensionMismatch` if the views differ in height.
+pub fn stack_horizontal<T: Copy>(left: &SliceView<T>, right: &SliceView<T>, dst: &mut [T]) -> Result<ImageDimensions, SliceViewError> {
+    if left.child_dims.rows != right.child_dims.rows {
+        return Err(SliceViewError::DimensionMismatch);
+    }
+    let dims = ImageDimensions::new(left.child_dims.columns + right.child_dims.columns, left.child_dims.rows);
+    assert!(dst.len() >= dims.columns * dims.rows, "dst too small for views");
+    let rows = left.rows().zip(right.rows());
+    let mut out = dst.iter_mut();
+    for (left_row, right_row) in rows {
+        // pixels first, so that no slot is consumed once the row runs out
+        for (&px, slot) in left_row.iter().chain(right_row.iter()).zip(&mut out) {
+            *slot = px;
+        }
+    }
+    Ok(dims)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+    use crate::ImageDimensions;
+
+    #[test]
+    fn normalize_unit_range() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3, 2));
+        let mut dst = [0f32; 6];
+        view.to_normalized_f32(0.0, 1.0 / 255.0, &mut dst);
+        assert!(dst.iter().all(|&val| (0.0..=1.0).contains(&val)));
+        assert!((dst[0] - 31.0 / 255.0).abs() < 1e-6);
+
+        let wide = [0u16, 255, 1000];
+        let view = SliceView::new_passthru(ImageDimensions::new(3, 1), &wide);
+        view.to_normalized_f32(1000.0, 0.5, &mut dst[..3]);
+        assert_eq!(dst[..3], [-500.0, -372.5, 0.0]);
+    }
+
+    #[test]
+    fn minmax_gradient() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3, 2));
+        let mut dst = [f32::NAN; 6];
+        view.normalize_minmax(&mut dst);
+        // 31 41 51 / 32 42 52
+        assert_eq!(dst[0], 0.0);
+        assert_eq!(dst[5], 1.0);
+        assert_eq!(dst[1], 10.0 / 21.0);
+        assert!(dst.iter().all(|&val| (0.0..=1.0).contains(&val)));
+
+        let flat = [7u8; 4];
+        SliceView::new_passthru(ImageDimensions::new(2, 2), &flat).normalize_minmax(&mut dst);
+        assert_eq!(dst[..4], [0.0; 4]);
+    }
+
+    #[test]
+    fn scale_12_bit() {
+        let parent = ImageDimensions::new(3, 2);
+        let frame = [4095u16, 0x0800, 0x0010, 15, 0xFFFF, 9];
+        let view = SliceView::new(parent, 0, 0, &frame, ImageDimensions::new(2, 2));
+        let mut dst = [0u8; 4];
+        view.to_u8_scaled(4, &mut dst);
+        assert_eq!(dst, [255, 128, 0, 255]);
+        view.to_u8_scaled(16, &mut dst);
+        assert_eq!(dst, [0; 4]);
+    }
+
+    #[test]
+    fn combine_max() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let dims = ImageDimensions::new(2, 2);
+        let a = SliceView::new(parent, 1, 2, &FRAME_64, dims);
+        let other = [40u8, 0, 35, 99];
+        let b = SliceView::new_passthru(dims, &other);
+        let mut dst = [0u8; 4];
+        combine_into(&a, &b, |&a, &b| a.max(b), &mut dst);
+        // a is 31, 41, 32, 42
+        assert_eq!(dst, [40, 41, 35, 99]);
+    }
+
+    #[test]
+    fn stack_regions() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let dims = ImageDimensions::new(2, 2);
+        let a = SliceView::new(parent, 1, 2, &FRAME_64, dims);
+        let b = SliceView::new(parent, 5, 0, &FRAME_64, dims);
+        let mut dst = [0u8; 8];
+
+        assert_eq!(stack_vertical(&a, &b, &mut dst), Ok(ImageDimensions::new(2, 4)));
+        assert_eq!(dst, [31, 41, 32, 42, 15, 25, 16, 26]);
+        assert_eq!(stack_horizontal(&a, &b, &mut dst), Ok(ImageDimensions::new(4, 2)));
+        // the second row starts with the second row of `a`
+        assert_eq!(dst, [31, 41, 15, 25, 32, 42, 16, 26]);
+
+        let tall = SliceView::new(parent, 0, 0, &FRAME_64, ImageDimensions::new(2, 3));
+        let wide = SliceView::new(parent, 0, 0, &FRAME_64, ImageDimensions::new(3, 2));
+        assert_eq!(stack_vertical(&a, &wide, &mut dst), Err(SliceViewError::DimensionMismatch));
+        assert_eq!(stack_horizontal(&a, &tall, &mut dst), Err(SliceViewError::DimensionMismatch));
+    }
+
+    #[test]
+    fn interleave_planes() {
+        let dims = ImageDimensions::new(2, 2);
+        let r = [1u8, 2, 3, 4];
+        let g = [10u8, 20, 30, 40];
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let b = SliceView::new(parent, 0, 0, &FRAME_64, dims);
+        let mut dst = [0u8; 12];
+        interleave3(
+            &SliceView::new_passthru(dims, &r),
+            &SliceView::new_passthru(dims, &g),
+            &b,
+            &mut dst,
+        );
+        assert_eq!(dst, [1, 10, 10, 2, 20, 20, 3, 30, 11, 4, 40, 21]);
+    }
+}
@@ -0,0 +1,116 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! Views of multi-byte pixels in raw byte buffers, decoded as they are read
+
+use crate::{byte_chunks, ImageDimensions, SliceView, SliceViewError};
+
+/// Order of the bytes within each multi-byte pixel
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// The least significant byte comes first
+    LittleEndian,
+    /// The most significant byte comes first, as in network byte order
+    BigEndian,
+}
+
+/// A view of 16 bit pixels stored as pairs of bytes in a known byte order,
+/// such as a frame received from the network, decoding each pixel as it is read
+/// rather than byte-swapping the whole buffer up front
+#[derive(Copy, Clone)]
+pub struct U16BytesView<'a> {
+    pixels: SliceView<'a, [u8; 2]>,
+    byte_order: ByteOrder,
+}
+
+impl<'a> U16BytesView<'a> {
+    /// View `bytes` as a densely packed `dims` image of `u16` pixels in `byte_order`.
+    /// Fails with `WrongLength` unless `bytes` holds exactly two bytes per pixel.
+    pub fn new(bytes: &'a [u8], dims: ImageDimensions, byte_order: ByteOrder) -> Result<Self, SliceViewError> {
+        let expected = 2 * dims.columns * dims.rows;
+        if bytes.len() != expected {
+            return Err(SliceViewError::WrongLength { expected, actual: bytes.len() });
+        }
+        let pairs = byte_chunks::<2>(bytes);
+        Ok(Self {
+            pixels: SliceView::new_passthru(dims, pairs),
+            byte_order,
+        })
+    }
+
+    /// Like `new`, for little-endian pixels
+    pub fn from_le_bytes_u16(bytes: &'a [u8], dims: ImageDimensions) -> Result<Self, SliceViewError> {
+        Self::new(bytes, dims, ByteOrder::LittleEndian)
+    }
+
+    /// Like `new`, for big-endian pixels
+    pub fn from_be_bytes_u16(bytes: &'a [u8], dims: ImageDimensions) -> Result<Self, SliceViewError> {
+        Self::new(bytes, dims, ByteOrder::BigEndian)
+    }
+
+    fn decode(&self, pair: [u8; 2]) -> u16 {
+        match self.byte_order {
+            ByteOrder::LittleEndian => u16::from_le_bytes(pair),
+            ByteOrder::BigEndian => u16::from_be_bytes(pair),
+        }
+    }
+
+    /// A view of the `dims` window with its top-left at local `(row, col)`
+    pub fn sub_view(&self, row: usize, col: usize, dims: ImageDimensions) -> Self {
+        Self {
+            pixels: self.pixels.sub_view(row, col, dims),
+            ..*self
+        }
+    }
+
+    /// Dimensions of the view
+    pub fn child_dims(&self) -> ImageDimensions {
+        self.pixels.child_dims
+    }
+
+    /// Number of pixels in the view
+    pub fn len(&self) -> usize {
+        self.pixels.len()
+    }
+
+    /// Whether the view has no pixels
+    pub fn is_empty(&self) -> bool {
+        self.pixels.is_empty()
+    }
+
+    /// The decoded pixel at local index `idx`, or `None` if beyond the end of the view
+    pub fn get(&self, idx: usize) -> Option<u16> {
+        self.pixels.get(idx).map(|&pair| self.decode(pair))
+    }
+
+    /// Iterate over the decoded pixels of the view in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = u16> + 'a {
+        let view = *self;
+        self.pixels.iter().map(move |&pair| view.decode(pair))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_big_endian() {
+        let bytes = [0x12u8, 0x34, 0x00, 0xFF, 0xAB, 0xCD, 0x80, 0x01];
+        let dims = ImageDimensions::new(2, 2);
+        let view = U16BytesView::from_be_bytes_u16(&bytes, dims).unwrap();
+        assert!(view.iter().eq([0x1234, 0x00FF, 0xABCD, 0x8001]));
+        assert_eq!(view.get(2), Some(0xABCD));
+        assert_eq!(view.get(4), None);
+        let column = view.sub_view(0, 1, ImageDimensions::new(1, 2));
+        assert_eq!(column.child_dims(), ImageDimensions::new(1, 2));
+        assert!(column.iter().eq([0x00FF, 0x8001]));
+
+        let little = U16BytesView::from_le_bytes_u16(&bytes, dims).unwrap();
+        assert_eq!(little.get(0), Some(0x3412));
+
+        let err = U16BytesView::from_be_bytes_u16(&bytes[..7], dims).err();
+        assert_eq!(err, Some(SliceViewError::WrongLength { expected: 8, actual: 7 }));
+    }
+}
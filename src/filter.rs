@@ -0,0 +1,378 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! Neighborhood filters over the pixels of a view
+
+use crate::SliceView;
+
+/// How samples that fall outside the view are treated
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EdgeMode {
+    /// Out-of-view samples are absent, and contribute nothing
+    None,
+    /// Out-of-view samples take the value of the nearest in-view pixel
+    Clamp,
+    /// Out-of-view samples wrap around to the opposite edge of the view
+    Wrap,
+}
+
+impl EdgeMode {
+    /// Resolve a possibly out-of-view position along an axis `len` pixels long
+    pub(crate) fn resolve(self, pos: isize, len: usize) -> Option<usize> {
+        if len == 0 {
+            return None;
+        }
+        if pos >= 0 && (pos as usize) < len {
+            return Some(pos as usize);
+        }
+        match self {
+            EdgeMode::None => None,
+            EdgeMode::Clamp => Some(if pos < 0 { 0 } else { len - 1 }),
+            EdgeMode::Wrap => Some(pos.rem_euclid(len as isize) as usize),
+        }
+    }
+}
+
+/// Correlate one line of `len` samples, fetched by `sample`, with `kernel`
+fn correlate_line<F: Fn(usize) -> i32>(
+    pos: usize,
+    len: usize,
+    kernel: &[i32],
+    mode: EdgeMode,
+    sample: F,
+) -> i32 {
+    let center = (kernel.len() / 2) as isize;
+    kernel
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &k)| {
+            mode.resolve(pos as isize + i as isize - center, len)
+                .map(|src| k * sample(src))
+        })
+        .sum()
+}
+
+/// One line of `len` samples, fetched by `sample`, blurred at `pos` by a Gaussian
+/// of standard deviation `sigma`. The kernel extends to `3 * sigma` samples,
+/// with out-of-line samples clamped, and `sigma <= 0` means no blur.
+fn gaussian_line<F: Fn(usize) -> f32>(pos: usize, len: usize, sigma: f32, sample: F) -> f32 {
+    if sigma <= 0.0 {
+        return sample(pos);
+    }
+    let radius = libm::ceilf(3.0 * sigma) as isize;
+    let mut sum = 0.0;
+    let mut norm = 0.0;
+    for d in -radius..=radius {
+        let weight = libm::expf(-((d * d) as f32) / (2.0 * sigma * sigma));
+        if let Some(src) = EdgeMode::Clamp.resolve(pos as isize + d, len) {
+            sum += weight * sample(src);
+        }
+        norm += weight;
+    }
+    sum / norm
+}
+
+impl<'a, T> SliceView<'a, T> {
+    /// The 3x3 neighborhood centered on local `(row, col)`, indexed `[row][col]`.
+    /// Positions outside the view are resolved by `mode`, and are `None` for `EdgeMode::None`.
+    pub fn neighborhood(&self, row: usize, col: usize, mode: EdgeMode) -> [[Option<&'a T>; 3]; 3] {
+        let mut hood = [[None; 3]; 3];
+        for (dy, hood_row) in hood.iter_mut().enumerate() {
+            let src_row = mode.resolve(row as isize + dy as isize - 1, self.child_dims.rows);
+            for (dx, slot) in hood_row.iter_mut().enumerate() {
+                let src_col = mode.resolve(col as isize + dx as isize - 1, self.child_dims.columns);
+                if let (Some(r), Some(c)) = (src_row, src_col) {
+                    *slot = Some(self.at(r, c));
+                }
+            }
+        }
+        hood
+    }
+}
+
+impl<'a, T: Copy + Into<i32>> SliceView<'a, T> {
+    /// Response of `kernel` at local `(center_row, center_col)`: the sum of each
+    /// kernel tap times the pixel under it, with the kernel's center
+    /// (`child_dims / 2`) over the given pixel.
+    /// Taps outside the view are resolved by `mode`, contributing nothing for `EdgeMode::None`.
+    pub fn weighted_sum_at(&self, center_row: usize, center_col: usize, kernel: &SliceView<i32>, mode: EdgeMode) -> i64 {
+        let kernel_rows = kernel.child_dims.rows;
+        let kernel_cols = kernel.child_dims.columns;
+        let mut sum = 0i64;
+        for ky in 0..kernel_rows {
+            let row = center_row as isize + ky as isize - (kernel_rows / 2) as isize;
+            let Some(row) = mode.resolve(row, self.child_dims.rows) else { continue };
+            for kx in 0..kernel_cols {
+                let col = center_col as isize + kx as isize - (kernel_cols / 2) as isize;
+                if let Some(col) = mode.resolve(col, self.child_dims.columns) {
+                    let px: i32 = (*self.at(row, col)).into();
+                    sum += *kernel.at(ky, kx) as i64 * px as i64;
+                }
+            }
+        }
+        sum
+    }
+
+    /// Filter the view with `h_kernel` along rows, then `v_kernel` along columns,
+    /// writing the densely packed result to `dst`.
+    /// `scratch` holds the intermediate horizontal pass and, like `dst`,
+    /// must be at least `len()` long.
+    ///
+    /// Kernel tap `i` is applied to the sample `i - kernel.len() / 2` pixels from the
+    /// output position, so odd-length kernels are centered, and they are applied
+    /// as written (not flipped). No normalization is applied to the result.
+    pub fn convolve_separable_with_scratch(
+        &self,
+        h_kernel: &[i32],
+        v_kernel: &[i32],
+        mode: EdgeMode,
+        scratch: &mut [i32],
+        dst: &mut [i32],
+    ) {
+        let columns = self.child_dims.columns;
+        let rows = self.child_dims.rows;
+        assert!(scratch.len() >= self.len(), "scratch too small for view");
+        assert!(dst.len() >= self.len(), "dst too small for view");
+
+        for row in 0..rows {
+            for col in 0..columns {
+                scratch[row * columns + col] =
+                    correlate_line(col, columns, h_kernel, mode, |src| {
+                        self[row * columns + src].into()
+                    });
+            }
+        }
+        for row in 0..rows {
+            for col in 0..columns {
+                dst[row * columns + col] =
+                    correlate_line(row, rows, v_kernel, mode, |src| {
+                        scratch[src * columns + col]
+                    });
+            }
+        }
+    }
+
+    /// Blur the view by a Gaussian of standard deviation `sigma`, as two passes of
+    /// its 1D kernel: along the rows into the densely packed `horizontal`, then along
+    /// the columns, calling `f` with the local index and value of each blurred pixel.
+    fn gaussian_blur<F: FnMut(usize, f32)>(&self, sigma: f32, horizontal: &mut [f32], mut f: F) {
+        let columns = self.child_dims.columns;
+        let rows = self.child_dims.rows;
+        for row in 0..rows {
+            for col in 0..columns {
+                horizontal[row * columns + col] = gaussian_line(col, columns, sigma, |src| {
+                    let px: i32 = (*self.at(row, src)).into();
+                    px as f32
+                });
+            }
+        }
+        for row in 0..rows {
+            for col in 0..columns {
+                f(row * columns + col, gaussian_line(row, rows, sigma, |src| horizontal[src * columns + col]));
+            }
+        }
+    }
+
+    /// Difference of Gaussians, for blob detection: write `blur(sigma1) - blur(sigma2)`
+    /// for each pixel, rounded and saturated, densely packed into `dst`.
+    /// With `sigma1 < sigma2`, bright blobs of about the size of `sigma1` respond
+    /// positively at their centers, and negatively in the surround.
+    /// The blurs clamp out-of-view samples to the edge pixels, and `sigma <= 0` means no blur.
+    /// Each blur is separable, taking `6 * sigma` steps per pixel for each of its two passes;
+    /// `scratch` holds the passes, and must be at least `2 * len()` long.
+    pub fn difference_of_gaussians_with_scratch(&self, sigma1: f32, sigma2: f32, scratch: &mut [f32], dst: &mut [i16]) {
+        let len = self.len();
+        assert!(scratch.len() >= 2 * len, "scratch too small for view");
+        assert!(dst.len() >= len, "dst too small for view");
+        let (horizontal, blurred) = scratch.split_at_mut(len);
+        self.gaussian_blur(sigma1, horizontal, |idx, value| blurred[idx] = value);
+        self.gaussian_blur(sigma2, horizontal, |idx, value| {
+            dst[idx] = libm::roundf(blurred[idx] - value) as i16;
+        });
+    }
+
+    /// Like `difference_of_gaussians_with_scratch`, allocating the intermediate buffers
+    #[cfg(feature = "alloc")]
+    pub fn difference_of_gaussians(&self, sigma1: f32, sigma2: f32, dst: &mut [i16]) {
+        let mut scratch = alloc::vec![0.0; 2 * self.len()];
+        self.difference_of_gaussians_with_scratch(sigma1, sigma2, &mut scratch, dst);
+    }
+
+    /// Like `convolve_separable_with_scratch`, allocating the intermediate buffer
+    #[cfg(feature = "alloc")]
+    pub fn convolve_separable(&self, h_kernel: &[i32], v_kernel: &[i32], mode: EdgeMode, dst: &mut [i32]) {
+        let mut scratch = alloc::vec![0; self.len()];
+        self.convolve_separable_with_scratch(h_kernel, v_kernel, mode, &mut scratch, dst);
+    }
+}
+
+impl<'a, T: Copy + Into<u32>> SliceView<'a, T> {
+    /// Horizontal pass of a box filter: write the sum of the `2 * radius + 1` pixels
+    /// centered on each pixel of its row, densely packed into `dst`.
+    /// Out-of-view samples are clamped to the edge pixels of the row.
+    ///
+    /// Each row keeps a running sum of its window, so the cost per pixel
+    /// does not depend on `radius`.
+    pub fn box_filter_h(&self, radius: usize, dst: &mut [u32]) {
+        let columns = self.child_dims.columns;
+        assert!(dst.len() >= self.len(), "dst too small for view");
+        if columns == 0 {
+            return;
+        }
+        let radius = radius as isize;
+        for (row, out_row) in dst.chunks_exact_mut(columns).take(self.child_dims.rows).enumerate() {
+            let px = |col: isize| -> u32 { (*self.at(row, EdgeMode::Clamp.resolve(col, columns).unwrap())).into() };
+            let mut sum: u32 = (-radius..=radius).map(px).sum();
+            for (col, out) in out_row.iter_mut().enumerate() {
+                *out = sum;
+                let col = col as isize;
+                sum = sum + px(col + radius + 1) - px(col - radius);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+    use crate::ImageDimensions;
+
+    #[test]
+    fn single_point_response() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3, 3));
+        let identity = [0, 0, 0, 0, 1, 0, 0, 0, 0];
+        let kernel = SliceView::new_passthru(ImageDimensions::new(3, 3), &identity);
+        assert_eq!(view.weighted_sum_at(1, 1, &kernel, EdgeMode::None), 42);
+        assert_eq!(view.weighted_sum_at(0, 2, &kernel, EdgeMode::None), 51);
+
+        let ones = [1; 9];
+        let kernel = SliceView::new_passthru(ImageDimensions::new(3, 3), &ones);
+        // only the 2x2 in-view corner contributes: 31, 41, 32, 42
+        assert_eq!(view.weighted_sum_at(0, 0, &kernel, EdgeMode::None), 146);
+        assert_eq!(view.weighted_sum_at(0, 0, &kernel, EdgeMode::Clamp), 4 * 31 + 2 * 41 + 2 * 32 + 42);
+    }
+
+    #[test]
+    fn identity_kernel() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3, 3));
+        let mut scratch = [0i32; 9];
+        let mut dst = [0i32; 9];
+        for &mode in &[EdgeMode::None, EdgeMode::Clamp, EdgeMode::Wrap] {
+            view.convolve_separable_with_scratch(&[0, 1, 0], &[0, 1, 0], mode, &mut scratch, &mut dst);
+            assert!(dst.iter().zip(view.iter()).all(|(&out, &px)| out == px as i32));
+        }
+    }
+
+    #[test]
+    fn box_kernel_edges() {
+        let data = [1u8, 2, 3, 4, 5, 6];
+        let view = SliceView::new_passthru(ImageDimensions::new(3, 2), &data);
+        let mut scratch = [0i32; 6];
+        let mut dst = [0i32; 6];
+        view.convolve_separable_with_scratch(&[1, 1, 1], &[1], EdgeMode::None, &mut scratch, &mut dst);
+        assert_eq!(dst, [3, 6, 5, 9, 15, 11]);
+        view.convolve_separable_with_scratch(&[1, 1, 1], &[1], EdgeMode::Clamp, &mut scratch, &mut dst);
+        assert_eq!(dst, [4, 6, 8, 13, 15, 17]);
+        view.convolve_separable_with_scratch(&[1], &[1, 1, 1], EdgeMode::Wrap, &mut scratch, &mut dst);
+        assert_eq!(dst, [9, 12, 15, 6, 9, 12]);
+    }
+
+    #[test]
+    fn sliding_box_sums() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(4, 3));
+        let mut dst = [0u32; 12];
+        view.box_filter_h(1, &mut dst);
+        // 31 41 51 61, with the edges clamped
+        assert_eq!(dst[..4], [103, 123, 153, 173]);
+
+        // agrees with the naive convolution for any radius
+        let mut scratch = [0i32; 12];
+        let mut naive = [0i32; 12];
+        for radius in 0..6 {
+            let kernel = [1; 11];
+            let kernel = &kernel[..2 * radius + 1];
+            view.convolve_separable_with_scratch(kernel, &[1], EdgeMode::Clamp, &mut scratch, &mut naive);
+            view.box_filter_h(radius, &mut dst);
+            assert!(dst.iter().zip(naive.iter()).all(|(&fast, &slow)| fast as i32 == slow));
+        }
+    }
+
+    #[test]
+    fn dog_center_surround() {
+        let mut spot = [0u8; 81];
+        spot[40] = 255;
+        let view = SliceView::new_passthru(ImageDimensions::new(9, 9), &spot);
+        let mut dst = [0i16; 81];
+        let mut scratch = [0.0f32; 2 * 81];
+        view.difference_of_gaussians_with_scratch(1.0, 2.0, &mut scratch, &mut dst);
+        // positive at the spot, negative a few pixels out, and symmetric
+        assert!(dst[40] > 0);
+        assert!(dst[40] > dst[41] && dst[41] > dst[42]);
+        for &idx in &[4 * 9 + 1, 4 * 9 + 7, 9 + 4, 7 * 9 + 4] {
+            assert!(dst[idx] < 0, "at {}", idx);
+            assert_eq!(dst[idx], dst[4 * 9 + 1]);
+        }
+
+        // the 1D passes match the full 2D kernel
+        let blur_2d = |row: usize, col: usize, sigma: f32| {
+            let weight = |d: isize| libm::expf(-((d * d) as f32) / (2.0 * sigma * sigma));
+            let (mut sum, mut norm) = (0.0, 0.0);
+            for dy in -6isize..=6 {
+                for dx in -6isize..=6 {
+                    let src_row = (row as isize + dy).clamp(0, 8) as usize;
+                    let src_col = (col as isize + dx).clamp(0, 8) as usize;
+                    sum += weight(dy) * weight(dx) * spot[src_row * 9 + src_col] as f32;
+                    norm += weight(dy) * weight(dx);
+                }
+            }
+            sum / norm
+        };
+        for (idx, &px) in dst.iter().enumerate() {
+            let (row, col) = (idx / 9, idx % 9);
+            let expected = blur_2d(row, col, 1.0) - blur_2d(row, col, 2.0);
+            assert!((px as f32 - expected).abs() <= 0.5 + 1e-3, "at {}", idx);
+        }
+
+        view.difference_of_gaussians_with_scratch(0.0, 0.0, &mut scratch, &mut dst);
+        assert_eq!(dst, [0; 81]);
+        view.difference_of_gaussians_with_scratch(0.0, 1.0, &mut scratch, &mut dst);
+        assert!(dst[40] > 0 && dst[41] < 0);
+        #[cfg(feature = "alloc")]
+        {
+            let mut allocated = [0i16; 81];
+            view.difference_of_gaussians(0.0, 1.0, &mut allocated);
+            assert_eq!(allocated, dst);
+        }
+    }
+
+    #[test]
+    fn corner_neighborhood() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3, 3));
+        let hood = view.neighborhood(0, 0, EdgeMode::None);
+        assert_eq!(hood[0], [None, None, None]);
+        assert_eq!(hood[1], [None, Some(&31), Some(&41)]);
+        assert_eq!(hood[2], [None, Some(&32), Some(&42)]);
+
+        let hood = view.neighborhood(0, 0, EdgeMode::Clamp);
+        assert_eq!(hood[0], [Some(&31), Some(&31), Some(&41)]);
+        let hood = view.neighborhood(2, 2, EdgeMode::Wrap);
+        assert_eq!(hood[2], [Some(&41), Some(&51), Some(&31)]);
+        assert_eq!(hood[1][1], Some(&53));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn allocating_variant() {
+        let data = [1u8, 2, 3, 4, 5, 6];
+        let view = SliceView::new_passthru(ImageDimensions::new(3, 2), &data);
+        let mut dst = [0i32; 6];
+        view.convolve_separable(&[1, 1, 1], &[1], EdgeMode::None, &mut dst);
+        assert_eq!(dst, [3, 6, 5, 9, 15, 11]);
+    }
+}
@@ -0,0 +1,194 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! Views of `embedded-graphics` framebuffers, and drawing into views.
+//! Framebuffers of 1, 8 and 16 bits per pixel can be viewed; there are no views
+//! of the other formats, whose pixels are neither whole bytes nor single bits.
+
+use core::convert::{Infallible, TryFrom};
+
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::framebuffer::{buffer_size, Framebuffer};
+use embedded_graphics::geometry::{OriginDimensions, Size};
+use embedded_graphics::pixelcolor::raw::{RawU1, RawU16, RawU8};
+use embedded_graphics::pixelcolor::PixelColor;
+use embedded_graphics::Pixel;
+
+use crate::{BitOrder, BitSliceView, ImageDimensions, SliceView, SliceViewMut};
+
+/// Number of bytes between the starts of adjacent rows of a framebuffer
+/// `width` pixels wide: rows of formats of under 8 bits per pixel are padded
+/// out to a whole number of bytes
+const fn line_length<C: PixelColor>(width: usize) -> usize {
+    buffer_size::<C>(width, 1)
+}
+
+impl<'a> SliceView<'a, u8> {
+    /// View the storage of an 8 bit per pixel framebuffer, such as one of `Gray8`,
+    /// as a `WIDTH` x `HEIGHT` image of one byte per pixel, stepping by its line length between rows
+    pub fn from_framebuffer<C, BO, const WIDTH: usize, const HEIGHT: usize, const N: usize>(fb: &'a Framebuffer<C, RawU8, BO, WIDTH, HEIGHT, N>) -> Self
+    where
+        C: PixelColor<Raw = RawU8>,
+    {
+        let dims = ImageDimensions::new(WIDTH, HEIGHT);
+        Self::new_with_stride(dims, line_length::<C>(WIDTH), 0, 0, fb.data(), dims)
+    }
+}
+
+impl<'a> SliceView<'a, [u8; 2]> {
+    /// View the storage of a 16 bit per pixel framebuffer, such as one of `Rgb565`,
+    /// as a `WIDTH` x `HEIGHT` image of the two bytes of each pixel, in the framebuffer's byte order,
+    /// stepping by its line length between rows
+    pub fn from_framebuffer<C, BO, const WIDTH: usize, const HEIGHT: usize, const N: usize>(fb: &'a Framebuffer<C, RawU16, BO, WIDTH, HEIGHT, N>) -> Self
+    where
+        C: PixelColor<Raw = RawU16>,
+    {
+        let dims = ImageDimensions::new(WIDTH, HEIGHT);
+        let pixels = crate::byte_chunks::<2>(fb.data());
+        Self::new_with_stride(dims, line_length::<C>(WIDTH) / 2, 0, 0, pixels, dims)
+    }
+}
+
+impl<'a> BitSliceView<'a> {
+    /// View the storage of a 1 bit per pixel framebuffer, such as one of `BinaryColor`,
+    /// as a `WIDTH` x `HEIGHT` image. Its rows are padded out to whole bytes,
+    /// with the leftmost pixel of each byte in the most significant bit.
+    pub fn from_framebuffer<C, BO, const WIDTH: usize, const HEIGHT: usize, const N: usize>(fb: &'a Framebuffer<C, RawU1, BO, WIDTH, HEIGHT, N>) -> Self
+    where
+        C: PixelColor<Raw = RawU1>,
+    {
+        let view = Self::new_passthru(ImageDimensions::new(WIDTH, HEIGHT), fb.data(), BitOrder::MsbFirst);
+        debug_assert_eq!(view.parent_stride(), line_length::<C>(WIDTH));
+        view
+    }
+}
+
+/// Draws `embedded-graphics` primitives into a mutable view, as returned by
+/// `SliceViewMut::as_draw_target`. Point `(0, 0)` is the top-left pixel of the view,
+/// and pixels drawn outside the view are discarded.
+pub struct ViewDrawTarget<'v, 'a, C> {
+    view: &'v mut SliceViewMut<'a, C>,
+}
+
+impl<'a, C: PixelColor> SliceViewMut<'a, C> {
+    /// Draw into the view with the `embedded-graphics` API, clipped to the view
+    pub fn as_draw_target(&mut self) -> ViewDrawTarget<'_, 'a, C> {
+        ViewDrawTarget { view: self }
+    }
+}
+
+impl<'v, 'a, C: PixelColor> OriginDimensions for ViewDrawTarget<'v, 'a, C> {
+    fn size(&self) -> Size {
+        let dims = self.view.child_dims;
+        Size::new(dims.columns as u32, dims.rows as u32)
+    }
+}
+
+impl<'v, 'a, C: PixelColor> DrawTarget for ViewDrawTarget<'v, 'a, C> {
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<C>>,
+    {
+        let dims = self.view.child_dims;
+        for Pixel(point, color) in pixels {
+            let (Ok(col), Ok(row)) = (usize::try_from(point.x), usize::try_from(point.y)) else { continue };
+            if col < dims.columns && row < dims.rows {
+                self.view[row * dims.columns + col] = color;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::framebuffer::{buffer_size, Framebuffer};
+    use embedded_graphics::pixelcolor::raw::{BigEndian, LittleEndian};
+    use embedded_graphics::pixelcolor::{Gray8, Rgb565};
+    use embedded_graphics::prelude::*;
+
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics::pixelcolor::BinaryColor;
+    use embedded_graphics::primitives::{Line, PrimitiveStyle, Rectangle};
+
+    use crate::{BitSliceView, ImageDimensions, SliceView, SliceViewMut};
+
+    #[test]
+    fn gray_framebuffer_window() {
+        let mut fb = Framebuffer::<Gray8, _, LittleEndian, 5, 4, { buffer_size::<Gray8>(5, 4) }>::new();
+        for y in 0..4 {
+            for x in 0..5 {
+                fb.set_pixel(Point::new(x, y), Gray8::new((10 * x + y) as u8));
+            }
+        }
+        let view = SliceView::<u8>::from_framebuffer(&fb);
+        assert_eq!(view.child_dims, ImageDimensions::new(5, 4));
+        assert_eq!(view.parent_stride(), 5);
+        let window = view.sub_view(1, 2, ImageDimensions::new(2, 2));
+        assert!(window.iter().eq([21, 31, 22, 32].iter()));
+    }
+
+    #[test]
+    fn rgb565_framebuffer_window() {
+        let mut fb = Framebuffer::<Rgb565, _, BigEndian, 3, 3, { buffer_size::<Rgb565>(3, 3) }>::new();
+        fb.set_pixel(Point::new(2, 1), Rgb565::RED);
+        fb.set_pixel(Point::new(1, 2), Rgb565::BLUE);
+        let view = SliceView::<[u8; 2]>::from_framebuffer(&fb);
+        let window = view.sub_view(1, 1, ImageDimensions::new(2, 2));
+        assert!(window.iter().eq([[0, 0], [0xF8, 0x00], [0x00, 0x1F], [0, 0]].iter()));
+    }
+
+    #[test]
+    fn binary_framebuffer_padded_lines() {
+        // 10 pixels of 1 bit take 2 bytes per line, 6 bits more than the pixels need
+        let mut fb = Framebuffer::<BinaryColor, _, LittleEndian, 10, 3, { buffer_size::<BinaryColor>(10, 3) }>::new();
+        for &(x, y) in &[(0, 0), (9, 0), (8, 1), (2, 2), (9, 2)] {
+            fb.set_pixel(Point::new(x, y), BinaryColor::On);
+        }
+        assert_eq!(fb.data().len(), 6);
+        let view = BitSliceView::from_framebuffer(&fb);
+        assert_eq!(view.parent_stride(), 2);
+        assert_eq!(view.child_dims, ImageDimensions::new(10, 3));
+        // a window over the last pixels of each line, just before its padding
+        let window = view.sub_view(0, 7, ImageDimensions::new(3, 3));
+        let lit: Vec<_> = (0..3)
+            .flat_map(|row| (0..3).map(move |col| (row, col)))
+            .filter(|&(row, col)| window.get_bit(row, col))
+            .collect();
+        assert_eq!(lit, [(0, 2), (1, 1), (2, 2)]);
+        let lower = view.sub_view(1, 0, ImageDimensions::new(10, 2));
+        assert!(lower.get_bit(1, 2) && !lower.get_bit(0, 0));
+    }
+
+    #[test]
+    fn draw_clipped_to_window() {
+        let parent = ImageDimensions::new(8, 5);
+        let mut frame = [BinaryColor::Off; 40];
+        let mut view = SliceViewMut::new(parent, 2, 1, &mut frame, ImageDimensions::new(4, 2));
+        // runs in off the left edge of the view, and out past its right edge
+        let line = Line::new(Point::new(-2, 0), Point::new(6, 1)).into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1));
+        let mut target = view.as_draw_target();
+        assert_eq!(target.bounding_box(), Rectangle::new(Point::zero(), Size::new(4, 2)));
+        line.draw(&mut target).unwrap();
+
+        // the same line, offset to the window and clipped to it
+        let mut expected = MockDisplay::new();
+        let window = Rectangle::new(Point::zero(), Size::new(4, 2));
+        line.draw(&mut expected.translated(Point::new(1, 2)).clipped(&window)).unwrap();
+
+        let mut drawn = MockDisplay::new();
+        let lit = frame.iter().enumerate().filter(|(_, &px)| px == BinaryColor::On);
+        drawn.draw_iter(lit.map(|(idx, &px)| Pixel(Point::new((idx % 8) as i32, (idx / 8) as i32), px))).unwrap();
+        drawn.assert_eq(&expected);
+        drawn.assert_pattern(&[
+            "     ",
+            "     ",
+            " ### ",
+            "    #",
+        ]);
+    }
+}
@@ -0,0 +1,84 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! Integral images, for constant-time sums over rectangles
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{ImageDimensions, SliceView};
+
+/// The summed-area table of a view: sums over any rectangle of the view
+/// take four lookups, whatever the size of the rectangle
+pub struct IntegralImage {
+    dims: ImageDimensions,
+    /// `(rows + 1) x (columns + 1)`, where entry `(r, c)` is the sum of the pixels
+    /// above and to the left of local `(r, c)`, exclusive; so the first row and column are zero
+    sums: Vec<u64>,
+}
+
+impl IntegralImage {
+    /// Dimensions of the view that the table was built from
+    pub fn dims(&self) -> ImageDimensions {
+        self.dims
+    }
+
+    fn at(&self, row: usize, col: usize) -> u64 {
+        self.sums[row * (self.dims.columns + 1) + col]
+    }
+
+    /// Sum of the pixels of the `dims` rectangle with its top-left at local `(top, left)`.
+    /// Panics if the rectangle does not lie within the view.
+    pub fn sum_rect(&self, top: usize, left: usize, dims: ImageDimensions) -> u64 {
+        let bottom = top + dims.rows;
+        let right = left + dims.columns;
+        assert!(bottom <= self.dims.rows && right <= self.dims.columns, "rectangle outside integral image");
+        self.at(bottom, right) + self.at(top, left) - self.at(top, right) - self.at(bottom, left)
+    }
+}
+
+impl<'a, T: Copy + Into<u64>> SliceView<'a, T> {
+    /// Build the integral image of the view, for repeated `sum_rect` queries
+    pub fn build_integral(&self) -> IntegralImage {
+        let columns = self.child_dims.columns;
+        let stride = columns + 1;
+        let mut sums = vec![0u64; stride * (self.child_dims.rows + 1)];
+        for row in 0..self.child_dims.rows {
+            let mut row_sum = 0;
+            for col in 0..columns {
+                row_sum += (*self.at(row, col)).into();
+                sums[(row + 1) * stride + col + 1] = sums[row * stride + col + 1] + row_sum;
+            }
+        }
+        IntegralImage { dims: self.child_dims, sums }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+
+    #[test]
+    fn rect_sums_match_brute_force() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(5, 4));
+        let integral = view.build_integral();
+        assert_eq!(integral.dims(), view.child_dims);
+
+        let rects = [
+            (0, 0, ImageDimensions::new(5, 4)),
+            (0, 0, ImageDimensions::new(1, 1)),
+            (3, 4, ImageDimensions::new(1, 1)),
+            (1, 2, ImageDimensions::new(3, 2)),
+            (2, 0, ImageDimensions::new(5, 2)),
+            (1, 1, ImageDimensions::new(0, 3)),
+        ];
+        for &(top, left, dims) in &rects {
+            let brute: u64 = view.sub_view(top, left, dims).iter().map(|&px| px as u64).sum();
+            assert_eq!(integral.sum_rect(top, left, dims), brute, "at ({}, {}) {:?}", top, left, dims);
+        }
+        assert_eq!(integral.sum_rect(0, 0, ImageDimensions::new(1, 1)), 31);
+    }
+}
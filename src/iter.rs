@@ -0,0 +1,196 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+use crate::{ImageDimensions, SliceView};
+
+/// Iterates over the pixels of a `SliceView` in row-major order
+pub struct Iter<'a, T> {
+    view: SliceView<'a, T>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new(view: SliceView<'a, T>) -> Self {
+        let back = view.len();
+        Self { view, front: 0, back }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front < self.back {
+            let idx = self.view.local_index_to_parent_index(self.front);
+            self.front += 1;
+            Some(&self.view.slice[idx])
+        }
+        else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.front < self.back {
+            self.back -= 1;
+            let idx = self.view.local_index_to_parent_index(self.back);
+            Some(&self.view.slice[idx])
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+/// A position within a `SliceView` that steps through its pixels in row-major order,
+/// tracking the parent index of the current pixel incrementally rather than
+/// recomputing it for each step. Unlike `Iter`, it can be moved with `seek`.
+pub struct ViewCursor<'a, T> {
+    view: SliceView<'a, T>,
+    row: usize,
+    col: usize,
+    parent_idx: isize,
+    /// Change in parent index for a step to the next local column
+    col_step: isize,
+    /// Change in parent index for a step from the end of a row to the start of the next
+    wrap_step: isize,
+}
+
+impl<'a, T> ViewCursor<'a, T> {
+    pub(crate) fn new(view: SliceView<'a, T>) -> Self {
+        let mut cursor = Self { view, row: 0, col: 0, parent_idx: 0, col_step: 0, wrap_step: 0 };
+        if !view.is_empty() {
+            // the parent index is affine in local (row, col), so two steps describe it
+            let parent_idx = |idx: usize| view.local_index_to_parent_index(idx) as isize;
+            let origin = parent_idx(0);
+            let columns = view.child_dims.columns;
+            cursor.col_step = if columns > 1 { parent_idx(1) - origin } else { 0 };
+            let row_step = if view.child_dims.rows > 1 { parent_idx(columns) - origin } else { 0 };
+            cursor.wrap_step = row_step - columns as isize * cursor.col_step;
+            cursor.parent_idx = origin;
+        }
+        cursor
+    }
+
+    /// Move the cursor so that `next` returns the pixel at local `(row, col)`.
+    /// A `row` beyond the last row of the view leaves the cursor exhausted.
+    /// Panics if `col` lies beyond the last column of the view.
+    pub fn seek(&mut self, row: usize, col: usize) {
+        assert!(col < self.view.child_dims.columns, "column {} outside view", col);
+        let row_step = self.wrap_step + self.view.child_dims.columns as isize * self.col_step;
+        self.parent_idx += (row as isize - self.row as isize) * row_step
+            + (col as isize - self.col as isize) * self.col_step;
+        self.row = row;
+        self.col = col;
+    }
+
+    /// Local `(row, col)` of the pixel that `next` returns
+    pub fn current_coords(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+}
+
+impl<'a, T> Iterator for ViewCursor<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.view.is_empty() || self.row >= self.view.child_dims.rows {
+            return None;
+        }
+        let px = &self.view.slice[self.parent_idx as usize];
+        self.col += 1;
+        self.parent_idx += self.col_step;
+        if self.col == self.view.child_dims.columns {
+            self.col = 0;
+            self.row += 1;
+            self.parent_idx += self.wrap_step;
+        }
+        Some(px)
+    }
+}
+
+/// Order in which to visit the pixels of a view
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScanOrder {
+    /// Left to right within each row, rows top to bottom
+    RowMajor,
+    /// Top to bottom within each column, columns left to right
+    ColumnMajor,
+    /// Like `RowMajor`, but odd rows run right to left, so each step
+    /// moves to an adjacent pixel
+    BoustrophedonRows,
+    /// Z-order: recursively the top-left, top-right, bottom-left and bottom-right
+    /// quadrants, which keeps pixels close in the image close in the order
+    Morton,
+}
+
+/// Gather the even bits of `code` into the low half
+fn compact_even_bits(code: usize) -> usize {
+    (0..usize::BITS / 2).fold(0, |acc, bit| acc | (((code >> (2 * bit)) & 1) << bit))
+}
+
+impl ScanOrder {
+    /// Number of steps in the scan of `dims`, some of which may be skipped
+    pub(crate) fn steps(self, dims: ImageDimensions) -> usize {
+        match self {
+            // the Morton curve covers the power-of-two square enclosing the view
+            ScanOrder::Morton if dims.columns > 0 && dims.rows > 0 => {
+                let side = dims.columns.max(dims.rows).next_power_of_two();
+                side * side
+            }
+            _ => dims.columns * dims.rows,
+        }
+    }
+
+    /// Local `(row, col)` visited at `step` of the scan of `dims`,
+    /// or `None` for a step that falls outside the view
+    pub(crate) fn coords(self, step: usize, dims: ImageDimensions) -> Option<(usize, usize)> {
+        let (columns, rows) = (dims.columns, dims.rows);
+        let (row, col) = match self {
+            ScanOrder::RowMajor => (step / columns, step % columns),
+            ScanOrder::ColumnMajor => (step % rows, step / rows),
+            ScanOrder::BoustrophedonRows => {
+                let row = step / columns;
+                let col = step % columns;
+                (row, if row % 2 == 1 { columns - 1 - col } else { col })
+            }
+            ScanOrder::Morton => (compact_even_bits(step >> 1), compact_even_bits(step)),
+        };
+        (row < rows && col < columns).then_some((row, col))
+    }
+
+    /// A later step of the scan of `dims` than `step`, which falls outside the view.
+    /// For the Morton curve this skips the whole of the largest aligned block
+    /// around `step` that lies outside the view, so that a scan visits only
+    /// a few such blocks for each pixel of the view, however oblong the view.
+    pub(crate) fn skip_outside(self, step: usize, dims: ImageDimensions) -> usize {
+        match self {
+            ScanOrder::Morton => {
+                // a block of 4^level steps covers a 2^level square; the block at the
+                // level of the whole curve has its origin at (0, 0), within the view
+                let (row, col) = (compact_even_bits(step >> 1), compact_even_bits(step));
+                let mut level = 0;
+                loop {
+                    let origin_mask = !((1 << (level + 1)) - 1);
+                    if row & origin_mask < dims.rows && col & origin_mask < dims.columns {
+                        break;
+                    }
+                    level += 1;
+                }
+                (step | ((1 << (2 * level)) - 1)) + 1
+            }
+            _ => step + 1,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! Views of images whose rows are separate slices
+
+use core::ops::Index;
+
+use crate::ImageDimensions;
+
+/// An image made up of separately stored rows, such as the scanlines
+/// produced one at a time by some decoders.
+/// The image is as wide as its shortest row, so that it is rectangular.
+#[derive(Copy, Clone)]
+pub struct JaggedView<'a, T> {
+    rows: &'a [&'a [T]],
+    pub child_dims: ImageDimensions,
+}
+
+impl<'a, T> JaggedView<'a, T> {
+    /// View `rows` as an image, with row `r` of the image being `rows[r]`
+    pub fn from_rows(rows: &'a [&'a [T]]) -> Self {
+        let columns = rows.iter().map(|row| row.len()).min().unwrap_or(0);
+        Self { rows, child_dims: ImageDimensions::new(columns, rows.len()) }
+    }
+
+    /// Number of pixels in the view
+    pub fn len(&self) -> usize {
+        self.child_dims.columns * self.child_dims.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The pixel at local index `idx`, or `None` if `idx` is beyond the end of the view
+    pub fn get(&self, idx: usize) -> Option<&'a T> {
+        if idx >= self.len() {
+            return None;
+        }
+        self.get_at(idx / self.child_dims.columns, idx % self.child_dims.columns)
+    }
+
+    /// The pixel at `(row, col)`, or `None` if that is outside the view
+    pub fn get_at(&self, row: usize, col: usize) -> Option<&'a T> {
+        if col >= self.child_dims.columns {
+            return None;
+        }
+        self.rows.get(row).map(|line| &line[col])
+    }
+
+    /// Iterate over the pixels of the view in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> {
+        let columns = self.child_dims.columns;
+        self.rows.iter().flat_map(move |line| line[..columns].iter())
+    }
+}
+
+impl<'a, T> Index<(usize, usize)> for JaggedView<'a, T> {
+    type Output = T;
+
+    /// The pixel at `(row, col)`. Panics if that is outside the view.
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        self.get_at(row, col).expect("pixel outside view")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JaggedView;
+    use crate::ImageDimensions;
+
+    #[test]
+    fn separate_rows() {
+        let first = [1u8, 2, 3, 4];
+        let second = [5u8, 6, 7];
+        let third = [8u8, 9, 10, 11, 12];
+        let rows = [&first[..], &second[..], &third[..]];
+        let view = JaggedView::from_rows(&rows);
+        assert_eq!(view.child_dims, ImageDimensions::new(3, 3));
+        assert_eq!(view[(1, 2)], 7);
+        assert_eq!(view.get(5), Some(&7));
+        assert_eq!(view.get_at(0, 3), None);
+        assert!(view.iter().eq([1, 2, 3, 5, 6, 7, 8, 9, 10].iter()));
+    }
+}
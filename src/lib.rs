@@ -7,10 +7,75 @@ LICENSE: BSD3 (see LICENSE file)
 //! Allows viewing a portion of an image, stored in a slice,
 //! as a smaller image, without copying data.
 //!
-use core::ops::{Index};
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::hash::{Hash, Hasher};
+use core::ops::Index;
+
+mod assert;
+mod bits;
+#[cfg(feature = "bytemuck")]
+mod cast;
+mod checksum;
+mod color;
+mod convert;
+mod endian;
+mod filter;
+#[cfg(feature = "embedded-graphics")]
+mod framebuffer;
+#[cfg(feature = "alloc")]
+mod integral;
+mod iter;
+mod jagged;
+#[cfg(feature = "alloc")]
+mod motion;
+#[cfg(feature = "std")]
+mod netpbm;
+#[cfg(feature = "alloc")]
+mod owned;
+#[cfg(feature = "rayon")]
+mod par;
+mod pixel_ops;
+#[cfg(feature = "alloc")]
+mod raw;
+mod resample;
+mod scalar;
+mod search;
+mod stats;
+mod view_mut;
+mod volatile;
+
+pub use assert::{check_view_eq, ViewMismatch};
+pub use bits::{BitOrder, BitSliceView};
+pub use checksum::DigestView;
+pub use color::{Rgb565, Rgb888};
+pub use convert::{combine_into, interleave3, stack_horizontal, stack_vertical};
+pub use endian::{ByteOrder, U16BytesView};
+pub use filter::EdgeMode;
+#[cfg(feature = "embedded-graphics")]
+pub use framebuffer::ViewDrawTarget;
+#[cfg(feature = "alloc")]
+pub use integral::IntegralImage;
+pub use iter::{Iter, ScanOrder, ViewCursor};
+pub use jagged::JaggedView;
+#[cfg(feature = "alloc")]
+pub use motion::MotionDetector;
+#[cfg(feature = "alloc")]
+pub use owned::{CowSliceView, OwnedSliceView};
+pub use pixel_ops::copy_strided;
+#[cfg(feature = "alloc")]
+pub use raw::ToBytes;
+pub use resample::Interpolation;
+pub use scalar::Scalar;
+pub use stats::{finalize_mean, RegionDiff};
+pub use view_mut::{IterMut, SliceViewMut};
+pub use volatile::VolatileSliceView;
 
 /// Used to specifiy cols x rows
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct ImageDimensions  {
     columns: usize,
     rows: usize
@@ -23,6 +88,115 @@ impl ImageDimensions {
             rows: height,
         }
     }
+
+    /// Width divided by height: infinite or NaN when there are no rows
+    pub fn aspect_ratio(&self) -> f32 {
+        self.columns as f32 / self.rows as f32
+    }
+
+    /// Whether these dimensions are no wider and no taller than `other`
+    pub fn fits_within(&self, other: &ImageDimensions) -> bool {
+        self.columns <= other.columns && self.rows <= other.rows
+    }
+}
+
+/// Errors from fallible view operations
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SliceViewError {
+    /// The requested window does not fit within the parent image or slice
+    OutOfBounds,
+    /// The rows of the view are not contiguous in the parent slice
+    NotContiguous,
+    /// The operation requires a square view
+    NotSquare,
+    /// The slice does not hold exactly the `expected` number of elements
+    WrongLength { expected: usize, actual: usize },
+    /// Serialized data does not start with a valid header for the element type
+    BadHeader,
+    /// The views do not have the dimensions the operation requires of them
+    DimensionMismatch,
+}
+
+impl core::fmt::Display for SliceViewError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SliceViewError::OutOfBounds => f.write_str("window does not fit within parent"),
+            SliceViewError::NotContiguous => f.write_str("view rows are not contiguous"),
+            SliceViewError::NotSquare => f.write_str("view is not square"),
+            SliceViewError::WrongLength { expected, actual } => {
+                write!(f, "slice of {} elements where {} expected", actual, expected)
+            }
+            SliceViewError::BadHeader => f.write_str("missing or mismatched header"),
+            SliceViewError::DimensionMismatch => f.write_str("views differ in dimensions"),
+        }
+    }
+}
+
+/// Check that a window of `dims` at the given origin lies within the parent image,
+/// and that `slice_len` elements are enough to back it
+fn check_window(
+    parent_dims: ImageDimensions,
+    parent_stride: usize,
+    start_row: usize,
+    start_col: usize,
+    dims: ImageDimensions,
+    slice_len: usize,
+) -> Result<(), SliceViewError> {
+    if start_col + dims.columns > parent_dims.columns || start_row + dims.rows > parent_dims.rows {
+        return Err(SliceViewError::OutOfBounds);
+    }
+    if dims.columns > 0 && dims.rows > 0 {
+        let last = (start_row + dims.rows - 1) * parent_stride + start_col + dims.columns - 1;
+        if last >= slice_len {
+            return Err(SliceViewError::OutOfBounds);
+        }
+    }
+    Ok(())
+}
+
+/// Origin and clipped dimensions of each `tile_dims` tile covering `dims`, in row-major order
+pub(crate) fn tile_grid(dims: ImageDimensions, tile_dims: ImageDimensions) -> impl Iterator<Item = (usize, usize, ImageDimensions)> {
+    (0..tile_count(dims, tile_dims)).map(move |tile_idx| tile_at(dims, tile_dims, tile_idx))
+}
+
+/// Number of `tile_dims` tiles covering `dims`, including clipped edge tiles
+pub(crate) fn tile_count(dims: ImageDimensions, tile_dims: ImageDimensions) -> usize {
+    assert!(tile_dims.columns > 0 && tile_dims.rows > 0, "tile dimensions must be nonzero");
+    dims.columns.div_ceil(tile_dims.columns) * dims.rows.div_ceil(tile_dims.rows)
+}
+
+/// Origin and clipped dimensions of tile `tile_idx` of those covering `dims`, in row-major order
+pub(crate) fn tile_at(dims: ImageDimensions, tile_dims: ImageDimensions, tile_idx: usize) -> (usize, usize, ImageDimensions) {
+    let across = dims.columns.div_ceil(tile_dims.columns);
+    let row = (tile_idx / across) * tile_dims.rows;
+    let col = (tile_idx % across) * tile_dims.columns;
+    let clipped = ImageDimensions::new(
+        tile_dims.columns.min(dims.columns - col),
+        tile_dims.rows.min(dims.rows - row),
+    );
+    (row, col, clipped)
+}
+
+/// The whole `N` byte chunks at the start of `bytes`, ignoring any trailing bytes
+pub(crate) fn byte_chunks<const N: usize>(bytes: &[u8]) -> &[[u8; N]] {
+    assert!(N > 0, "chunk size must be nonzero");
+    // [u8; N] has the alignment of u8, and the chunks lie within `bytes`
+    unsafe { core::slice::from_raw_parts(bytes.as_ptr() as *const [u8; N], bytes.len() / N) }
+}
+
+/// Translate a local (child) index into an index in the parent slice
+pub(crate) fn translate(
+    idx: usize,
+    child_columns: usize,
+    parent_start_row: usize,
+    parent_start_col: usize,
+    parent_stride: usize,
+) -> usize {
+    let child_y = idx / child_columns;
+    let child_x = idx % child_columns;
+    let frame_x = parent_start_col + child_x;
+    let frame_y = parent_start_row + child_y;
+    frame_y * parent_stride + frame_x
 }
 
 pub struct SliceView<'a, T> {
@@ -31,17 +205,43 @@ pub struct SliceView<'a, T> {
     pub child_dims: ImageDimensions,
     parent_start_col: usize,
     parent_start_row: usize,
+    /// Number of elements between the starts of adjacent parent rows
+    parent_stride: usize,
+    /// Number of elements between adjacent pixels of a parent row
+    pixel_stride: usize,
+    /// Whether the view's rows are the columns of the window in the parent
+    transposed: bool,
+    /// Whether the rows of the window are read bottom to top
+    flip_window_rows: bool,
+    /// Whether the columns of the window are read right to left
+    flip_window_cols: bool,
     slice: &'a [T],
 }
 
 impl<'a, T> SliceView<'a, T> {
     pub fn new( parent_dims: ImageDimensions, parent_start_row: usize, parent_start_col: usize, slice: &'a [T], child_dims: ImageDimensions) -> Self {
+        Self::new_with_stride(parent_dims, parent_dims.columns, parent_start_row, parent_start_col, slice, child_dims)
+    }
+
+    /// Like `new`, but for parent images whose rows are padded:
+    /// `parent_stride` is the number of elements between the starts of adjacent rows.
+    pub fn new_with_stride(parent_dims: ImageDimensions, parent_stride: usize, parent_start_row: usize, parent_start_col: usize, slice: &'a [T], child_dims: ImageDimensions) -> Self {
+        #[cfg(feature = "debug-checks")]
+        debug_assert!(
+            check_window(parent_dims, parent_stride, parent_start_row, parent_start_col, child_dims, slice.len()).is_ok(),
+            "view window does not fit within parent"
+        );
         Self {
             passthru: false,
             parent_dims,
             child_dims,
             parent_start_col,
             parent_start_row,
+            parent_stride,
+            pixel_stride: 1,
+            transposed: false,
+            flip_window_rows: false,
+            flip_window_cols: false,
             slice
         }
     }
@@ -50,12 +250,39 @@ impl<'a, T> SliceView<'a, T> {
     pub fn new_passthru(parent_dims: ImageDimensions, slice: &'a [T]) -> Self {
         Self {
             passthru: true,
-            parent_dims,
-            child_dims: parent_dims,
-            parent_start_col: 0,
-            parent_start_row: 0,
-            slice
+            ..Self::new(parent_dims, 0, 0, slice, parent_dims)
+        }
+    }
+
+    /// Like `new_passthru`, but checked: fails unless `slice` holds exactly
+    /// one element per pixel of `dims`, catching truncated or oversized frames.
+    pub fn try_passthru(slice: &'a [T], dims: ImageDimensions) -> Result<Self, SliceViewError> {
+        let expected = dims.columns * dims.rows;
+        if slice.len() != expected {
+            return Err(SliceViewError::WrongLength { expected, actual: slice.len() });
         }
+        Ok(Self::new_passthru(dims, slice))
+    }
+
+    /// Up to `requested` pixels of the parent starting at `(parent_start_row, parent_start_col)`:
+    /// like `new`, with `child_dims` truncated so that the window never extends beyond
+    /// the parent image. An origin outside the parent gives an empty view,
+    /// with its origin moved onto the parent's edge.
+    pub fn fit_from_origin(parent_dims: ImageDimensions, parent_start_row: usize, parent_start_col: usize, requested: ImageDimensions, slice: &'a [T]) -> Self {
+        let start_row = parent_start_row.min(parent_dims.rows);
+        let start_col = parent_start_col.min(parent_dims.columns);
+        let child_dims = ImageDimensions::new(
+            requested.columns.min(parent_dims.columns - start_col),
+            requested.rows.min(parent_dims.rows - start_row),
+        );
+        Self::new(parent_dims, start_row, start_col, slice, child_dims)
+    }
+
+    /// Wrap a fixed-size 2D array, `H` rows of `W` columns, as a passthru view
+    pub fn from_array_2d<const W: usize, const H: usize>(arr: &'a [[T; W]; H]) -> Self {
+        // the rows of a 2D array are laid out one after another, without padding
+        let pixels = unsafe { core::slice::from_raw_parts(arr.as_ptr() as *const T, W * H) };
+        Self::new_passthru(ImageDimensions::new(W, H), pixels)
     }
 
     /// Split the parent slice into two same-size children, by column, starting at the given position in the parent
@@ -63,48 +290,716 @@ impl<'a, T> SliceView<'a, T> {
         let second_child_start_col = parent_start_col + child_dims.columns;
 
         (
+        Self::new(parent_dims, parent_start_row, parent_start_col, slice, child_dims),
+        Self::new(parent_dims, parent_start_row, second_child_start_col, slice, child_dims)
+        )
+    }
+
+    /// Split the parent slice into `n` adjacent children, each `strip_cols` wide and
+    /// `child_rows` tall, starting at the given position in the parent.
+    /// Fails if the strips do not all fit within the parent.
+    #[cfg(feature = "alloc")]
+    pub fn try_split_cols(parent_dims: ImageDimensions, parent_start_row: usize, parent_start_col: usize, slice: &'a [T], child_rows: usize, n: usize, strip_cols: usize) -> Result<alloc::vec::Vec<Self>, SliceViewError> {
+        let child_dims = ImageDimensions::new(strip_cols, child_rows);
+        let all_dims = ImageDimensions::new(n * strip_cols, child_rows);
+        check_window(parent_dims, parent_dims.columns, parent_start_row, parent_start_col, all_dims, slice.len())?;
+        Ok((0..n)
+            .map(|i| Self::new(parent_dims, parent_start_row, parent_start_col + i * strip_cols, slice, child_dims))
+            .collect())
+    }
+
+    /// Like `try_split_cols`, but returning an array of `N` strips, for use without an allocator
+    pub fn try_split_cols_array<const N: usize>(parent_dims: ImageDimensions, parent_start_row: usize, parent_start_col: usize, slice: &'a [T], child_rows: usize, strip_cols: usize) -> Result<[Self; N], SliceViewError> {
+        let child_dims = ImageDimensions::new(strip_cols, child_rows);
+        let all_dims = ImageDimensions::new(N * strip_cols, child_rows);
+        check_window(parent_dims, parent_dims.columns, parent_start_row, parent_start_col, all_dims, slice.len())?;
+        Ok(core::array::from_fn(|i| {
+            Self::new(parent_dims, parent_start_row, parent_start_col + i * strip_cols, slice, child_dims)
+        }))
+    }
+
+    /// Move the top-left of the view to `(row, col)` in the same parent.
+    /// As with `new`, the new origin is not validated: a window pushed past the
+    /// right edge of the parent wraps into the following rows, and accessing a
+    /// pixel beyond the end of the parent slice panics.
+    pub fn set_origin(&mut self, row: usize, col: usize) {
+        self.passthru = false;
+        self.parent_start_row = row;
+        self.parent_start_col = col;
+    }
+
+    /// Builder variant of `set_origin`
+    pub fn with_origin(mut self, row: usize, col: usize) -> Self {
+        self.set_origin(row, col);
+        self
+    }
+
+    /// Replace the assumed number of elements between the starts of adjacent
+    /// parent rows, for parents discovered to have padded rows
+    pub fn with_parent_stride(mut self, stride: usize) -> Self {
+        assert!(stride >= self.parent_dims.columns, "stride narrower than parent");
+        self.passthru = false;
+        self.parent_stride = stride;
+        self
+    }
+
+    /// Step `stride` elements between adjacent pixels of a row, rather than one,
+    /// for example to view one channel of interleaved data, or pixels each preceded
+    /// by metadata. Parent columns and the view's origin then count pixels, not elements,
+    /// while the parent stride still counts the elements between the starts of rows.
+    pub fn with_pixel_stride(mut self, stride: usize) -> Self {
+        assert!(stride > 0, "pixel stride must be nonzero");
+        self.passthru = false;
+        self.pixel_stride = stride;
+        self
+    }
+
+    /// Whether the parent slice is long enough to back every pixel of the view
+    pub fn is_valid(&self) -> bool {
+        self.last_parent_index().map_or(true, |last| last < self.slice.len())
+    }
+
+    /// Number of pixels of the view that are backed by the parent slice, counted
+    /// along each of the parent rows that the view's window spans: less than `len`
+    /// for a frame that arrived truncated, which is missing the end of the
+    /// window's bottom rows in the parent, whatever the orientation of the view
+    pub fn accessible_len(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let (rows, cols) = self.window_extent();
+        (0..rows)
+            .map(|row| {
+                let start = (self.parent_start_row + row) * self.parent_stride
+                    + self.parent_start_col * self.pixel_stride;
+                match self.slice.len().checked_sub(start) {
+                    Some(backed) if backed > 0 => cols.min((backed - 1) / self.pixel_stride + 1),
+                    _ => 0,
+                }
+            })
+            .sum()
+    }
+
+    /// Iterate over the pixels of the view in row-major order, skipping
+    /// those that are not backed by the parent slice rather than panicking:
+    /// yields `accessible_len` pixels
+    pub fn iter_accessible(&self) -> impl Iterator<Item = &'a T> {
+        let view = *self;
+        (0..self.len()).filter_map(move |idx| view.get(idx))
+    }
+
+    /// Number of pixels in the view
+    pub fn len(&self) -> usize {
+        self.child_dims.columns * self.child_dims.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over the pixels of the view in row-major order
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter::new(*self)
+    }
+
+    /// A cursor over the pixels of the view, starting at local `(0, 0)`
+    pub fn cursor(&self) -> ViewCursor<'a, T> {
+        ViewCursor::new(*self)
+    }
+
+    /// Iterate over the pixels of the view in row-major order,
+    /// along with the index of each pixel in the parent slice
+    pub fn parent_indexed(&self) -> impl Iterator<Item = (usize, &'a T)> {
+        let view = *self;
+        (0..self.len()).map(move |idx| {
+            let parent_idx = view.local_index_to_parent_index(idx);
+            (parent_idx, &view.slice[parent_idx])
+        })
+    }
+
+    /// The local index of the pixel at local `(row, col)`, as used by `Index<usize>`:
+    /// pixels are numbered in row-major order, `row * child_dims.columns + col`.
+    /// Like indexing itself, this does not check that `col` lies within the row.
+    pub fn row_major_index(&self, row: usize, col: usize) -> usize {
+        row * self.child_dims.columns + col
+    }
+
+    /// Like `row_major_index`, but `None` if `(row, col)` lies outside the view
+    pub fn checked_row_major_index(&self, row: usize, col: usize) -> Option<usize> {
+        if row >= self.child_dims.rows || col >= self.child_dims.columns {
+            return None;
+        }
+        Some(self.row_major_index(row, col))
+    }
+
+    /// The pixel at local index `idx`, or `None` if `idx` is beyond the end of the view
+    /// or the parent slice. As with indexing, pixels of a window that overruns the
+    /// right edge of the parent wrap into the following parent row.
+    pub fn get(&self, idx: usize) -> Option<&'a T> {
+        if idx >= self.len() {
+            return None;
+        }
+        self.slice.get(self.local_index_to_parent_index(idx))
+    }
+
+    /// The pixels at each of the local `indices`, as fetched by `get`,
+    /// for example to sample a fixed pattern of points
+    pub fn get_many<const N: usize>(&self, indices: [usize; N]) -> [Option<&'a T>; N] {
+        indices.map(|idx| self.get(idx))
+    }
+
+    /// Like `get`, but treating pixels that fall outside the parent image
+    /// as out of bounds, rather than wrapping into the following parent row
+    pub fn get_strict(&self, idx: usize) -> Option<&'a T> {
+        if idx >= self.len() {
+            return None;
+        }
+        let (row, col) = self.parent_coords(idx);
+        if row >= self.parent_dims.rows || col >= self.parent_dims.columns {
+            return None;
+        }
+        self.slice.get(self.local_index_to_parent_index(idx))
+    }
+
+    /// The pixel at local index `idx`, or `default` where `get_strict` finds none:
+    /// beyond the end of the view, outside the parent image, or beyond the parent slice.
+    /// Suits padding by a constant when sampling near the edges of a view.
+    pub fn get_or(&self, idx: usize, default: &'a T) -> &'a T {
+        self.get_strict(idx).unwrap_or(default)
+    }
+
+    /// Like `get_or`, for the pixel at local `(row, col)`
+    pub fn get_or_at(&self, row: usize, col: usize, default: &'a T) -> &'a T {
+        match self.checked_row_major_index(row, col) {
+            Some(idx) => self.get_or(idx, default),
+            None => default,
+        }
+    }
+
+    /// Like indexing, but panics for pixels that fall outside the parent image
+    /// rather than wrapping into the following parent row
+    pub fn index_strict(&self, idx: usize) -> &'a T {
+        match self.get_strict(idx) {
+            Some(px) => px,
+            None => panic!("index {} is outside the view or its parent", idx),
+        }
+    }
+
+    /// Number of `(rows, columns)` of the view's window in the parent
+    fn window_extent(&self) -> (usize, usize) {
+        if self.transposed {
+            (self.child_dims.columns, self.child_dims.rows)
+        }
+        else {
+            (self.child_dims.rows, self.child_dims.columns)
+        }
+    }
+
+    /// Position `(row, col)` within the window of the pixel at local `(row, col)`
+    fn window_coords(&self, row: usize, col: usize) -> (usize, usize) {
+        let (window_row, window_col) = if self.transposed { (col, row) } else { (row, col) };
+        let (window_rows, window_cols) = self.window_extent();
+        (
+            if self.flip_window_rows { window_rows - 1 - window_row } else { window_row },
+            if self.flip_window_cols { window_cols - 1 - window_col } else { window_col },
+        )
+    }
+
+    /// Position `(row, col)` within the parent image of the pixel at local index `idx`
+    fn parent_coords(&self, idx: usize) -> (usize, usize) {
+        let row = idx / self.child_dims.columns;
+        let col = idx % self.child_dims.columns;
+        let (window_row, window_col) = self.window_coords(row, col);
+        (self.parent_start_row + window_row, self.parent_start_col + window_col)
+    }
+
+    /// The pixel at local `(row, col)`, borrowed for the lifetime of the parent slice
+    pub(crate) fn at(&self, row: usize, col: usize) -> &'a T {
+        &self.slice[self.local_index_to_parent_index(self.row_major_index(row, col))]
+    }
+
+    /// The pixels of one row of the view, where these are contiguous in the parent slice.
+    /// This is not the case for transposed or mirrored views, nor for rows that overrun
+    /// the right edge of the parent and so wrap into the following parent row.
+    pub(crate) fn row_slice(&self, row: usize) -> Option<&'a [T]> {
+        if self.transposed
+            || self.flip_window_cols
+            || self.pixel_stride != 1
+            || self.is_empty()
+            || row >= self.child_dims.rows
+            || self.parent_start_col + self.child_dims.columns > self.parent_dims.columns
+        {
+            return None;
+        }
+        let start = self.local_index_to_parent_index(row * self.child_dims.columns);
+        self.slice.get(start..start + self.child_dims.columns)
+    }
+
+    /// Split the pixels of one row of the view into an unaligned prefix,
+    /// a middle that starts at an `align`-byte boundary and is a whole number
+    /// of `align` bytes long, and an unaligned suffix, as `slice::align_to` does.
+    /// This suits SIMD code that processes the middle in aligned chunks.
+    /// Returns `None` where the row is not contiguous in the parent (see `row_slice`).
+    /// Panics unless `align` is a power of two and a multiple of the size of `T`.
+    pub fn row_slice_aligned(&self, row: usize, align: usize) -> Option<(&'a [T], &'a [T], &'a [T])> {
+        let elem_size = core::mem::size_of::<T>();
+        assert!(align.is_power_of_two() && elem_size > 0 && align % elem_size == 0,
+            "alignment must be a power of two multiple of the element size");
+        let line = self.row_slice(row)?;
+        let prefix = line.as_ptr().align_offset(align).min(line.len());
+        let chunk = align / elem_size;
+        let middle = (line.len() - prefix) / chunk * chunk;
+        let (head, rest) = line.split_at(prefix);
+        let (middle, tail) = rest.split_at(middle);
+        Some((head, middle, tail))
+    }
+
+    /// Number of rows yielded by the row iterators: none for an empty view
+    fn row_count(&self) -> usize {
+        if self.is_empty() { 0 } else { self.child_dims.rows }
+    }
+
+    /// Iterate over the rows of the view, each as a one-row view
+    pub fn rows(&self) -> impl Iterator<Item = SliceView<'a, T>> {
+        let view = *self;
+        let row_dims = ImageDimensions::new(self.child_dims.columns, 1);
+        (0..self.row_count()).map(move |row| view.sub_view(row, 0, row_dims))
+    }
+
+    /// Iterate over the rows of the view that are contiguous in the parent slice,
+    /// each with its local row index. Rows that are not contiguous
+    /// (see `row_slice`) are skipped: for a transposed view, or a window
+    /// overrunning the right edge of the parent, that is all of them.
+    pub fn enumerate_rows(&self) -> impl Iterator<Item = (usize, &'a [T])> {
+        let view = *self;
+        (0..self.row_count()).filter_map(move |row| view.row_slice(row).map(|line| (row, line)))
+    }
+
+    /// Split the view into horizontal bands at rows for which `is_separator` is true,
+    /// yielding a sub-view for each run of non-separator rows, top to bottom.
+    /// Each row is passed to `is_separator` once, as a one-row view (see `rows`).
+    /// Separator rows are not part of any band, and no empty bands are yielded.
+    pub fn split_rows_where<F: FnMut(SliceView<'a, T>) -> bool>(&self, is_separator: F) -> impl Iterator<Item = SliceView<'a, T>> {
+        let view = *self;
+        let row_count = self.row_count();
+        let mut rows = self.rows().map(is_separator).enumerate();
+        core::iter::from_fn(move || {
+            let (start, _) = rows.find(|&(_, separator)| !separator)?;
+            // the separator row ending the band, if any, is consumed along with it
+            let end = rows.find(|&(_, separator)| separator).map_or(row_count, |(row, _)| row);
+            Some(view.sub_view(start, 0, ImageDimensions::new(view.child_dims.columns, end - start)))
+        })
+    }
+
+    /// Call `f(row_index, row_slice)` for each row of the view, in order,
+    /// for example to stream the view out one scanline at a time.
+    /// Rows that overrun the right edge of the parent are clipped to it,
+    /// rather than wrapping into the following parent row.
+    /// Fails before calling `f` if the view's rows are not contiguous in the parent
+    /// (it is transposed or mirrored, or has a pixel stride) or extend beyond the parent slice.
+    pub fn for_each_row<F: FnMut(usize, &[T])>(&self, mut f: F) -> Result<(), SliceViewError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        if self.transposed || self.flip_window_cols || self.pixel_stride != 1 {
+            return Err(SliceViewError::NotContiguous);
+        }
+        let columns = self.child_dims.columns
+            .min(self.parent_dims.columns.saturating_sub(self.parent_start_col));
+        let first_start = self.local_index_to_parent_index(0);
+        let last_start = self.local_index_to_parent_index((self.child_dims.rows - 1) * self.child_dims.columns);
+        if first_start.max(last_start) + columns > self.slice.len() {
+            return Err(SliceViewError::OutOfBounds);
+        }
+        for row in 0..self.child_dims.rows {
+            let start = self.local_index_to_parent_index(row * self.child_dims.columns);
+            f(row, &self.slice[start..start + columns]);
+        }
+        Ok(())
+    }
+
+    /// Iterate over the pixels of the view column by column:
+    /// top-to-bottom within each column, left-to-right across columns
+    pub fn iter_col_major(&self) -> impl Iterator<Item = &'a T> {
+        let view = *self;
+        let columns = self.child_dims.columns;
+        let rows = self.child_dims.rows;
+        (0..columns)
+            .flat_map(move |col| (0..rows).map(move |row| row * columns + col))
+            .map(move |idx| &view.slice[view.local_index_to_parent_index(idx)])
+    }
+
+    /// Iterate over the pixels of the view in the given `order`,
+    /// along with their local `(row, col)`. Every pixel is visited exactly once.
+    pub fn iter_order(&self, order: ScanOrder) -> impl Iterator<Item = (usize, usize, &'a T)> {
+        let view = *self;
+        let dims = self.child_dims;
+        let steps = order.steps(dims);
+        let mut step = 0;
+        core::iter::from_fn(move || {
+            while step < steps {
+                let coords = order.coords(step, dims);
+                match coords {
+                    Some((row, col)) => {
+                        step += 1;
+                        return Some((row, col, view.at(row, col)));
+                    }
+                    None => step = order.skip_outside(step, dims),
+                }
+            }
+            None
+        })
+    }
+
+    /// A view of the `dims` window of this view with its top-left at local `(row, col)`.
+    ///
+    /// As with indexing, a sub-window that extends beyond the right or bottom of this
+    /// view reads on into the parent. For a flipped view that would read past the
+    /// near edge of the window instead, so the sub-window is clipped to the view
+    /// along each flipped axis.
+    pub fn sub_view(&self, row: usize, col: usize, dims: ImageDimensions) -> SliceView<'a, T> {
+        let (mut window_row, mut window_col) = if self.transposed { (col, row) } else { (row, col) };
+        let (window_rows, window_cols) = self.window_extent();
+        let sub = Self { child_dims: dims, ..*self };
+        let (mut sub_rows, mut sub_cols) = sub.window_extent();
+        // a mirrored sub-window starts from the far edge of this window
+        if self.flip_window_rows {
+            let available = window_rows.saturating_sub(window_row);
+            sub_rows = sub_rows.min(available);
+            window_row = available - sub_rows;
+        }
+        if self.flip_window_cols {
+            let available = window_cols.saturating_sub(window_col);
+            sub_cols = sub_cols.min(available);
+            window_col = available - sub_cols;
+        }
+        let dims = if self.transposed {
+            ImageDimensions::new(sub_rows, sub_cols)
+        }
+        else {
+            ImageDimensions::new(sub_cols, sub_rows)
+        };
         Self {
             passthru: false,
-            parent_dims,
-            child_dims,
-            parent_start_col,
-            parent_start_row,
-            slice
-        },
+            child_dims: dims,
+            parent_start_row: self.parent_start_row + window_row,
+            parent_start_col: self.parent_start_col + window_col,
+            ..*self
+        }
+    }
+
+    /// This view with its dimensions capped at `max_dims`, keeping its top-left pixel
+    pub fn clamp_dims(&self, max_dims: ImageDimensions) -> SliceView<'a, T> {
+        let dims = ImageDimensions::new(
+            self.child_dims.columns.min(max_dims.columns),
+            self.child_dims.rows.min(max_dims.rows),
+        );
+        self.sub_view(0, 0, dims)
+    }
+
+    /// Split the view into non-overlapping tiles of `tile_dims`, in row-major order.
+    /// Tiles along the right and bottom edges are clipped to fit within the view.
+    pub fn tiles(&self, tile_dims: ImageDimensions) -> impl Iterator<Item = SliceView<'a, T>> {
+        let view = *self;
+        self.tile_coords(tile_dims)
+            .map(move |(row, col, dims)| view.sub_view(row, col, dims))
+    }
+
+    /// A sub-view for each `(start_row, start_col, dims)` rectangle in local coordinates,
+    /// such as the boxes found by a detector, in the order given.
+    /// Rectangles that do not lie entirely within the view are skipped, not clipped.
+    pub fn rois<'r>(&self, rects: &'r [(usize, usize, ImageDimensions)]) -> impl Iterator<Item = SliceView<'a, T>> + 'r
+    where
+        'a: 'r,
+    {
+        let view = *self;
+        rects.iter()
+            .filter(move |&&(row, col, dims)| {
+                row.checked_add(dims.rows).is_some_and(|end| end <= view.child_dims.rows)
+                    && col.checked_add(dims.columns).is_some_and(|end| end <= view.child_dims.columns)
+            })
+            .map(move |&(row, col, dims)| view.sub_view(row, col, dims))
+    }
+
+    /// Divide the view into `ROWS` x `COLS` equal cells, indexed `[row][col]`,
+    /// for example to address the sprites of a sprite sheet.
+    /// Panics unless the view divides evenly into the cells.
+    pub fn grid<const ROWS: usize, const COLS: usize>(&self) -> [[SliceView<'a, T>; COLS]; ROWS] {
+        assert!(ROWS > 0 && COLS > 0, "grid must have cells");
+        assert!(
+            self.child_dims.rows % ROWS == 0 && self.child_dims.columns % COLS == 0,
+            "view does not divide evenly into grid"
+        );
+        let cell = ImageDimensions::new(self.child_dims.columns / COLS, self.child_dims.rows / ROWS);
+        core::array::from_fn(|row| {
+            core::array::from_fn(|col| self.sub_view(row * cell.rows, col * cell.columns, cell))
+        })
+    }
+
+    /// Copy out each `W` x `H` block of the view, as an array of `H` rows,
+    /// in the row-major order of `tiles`. Partial blocks at the right and bottom edges are skipped.
+    pub fn blocks<const W: usize, const H: usize>(&self) -> impl Iterator<Item = [[T; W]; H]> + 'a
+    where
+        T: Copy,
+    {
+        let view = *self;
+        self.tile_coords(ImageDimensions::new(W, H))
+            .filter(|&(_, _, dims)| dims == ImageDimensions::new(W, H))
+            .map(move |(row, col, _)| {
+                core::array::from_fn(|y| core::array::from_fn(|x| *view.at(row + y, col + x)))
+            })
+    }
+
+    /// The local `(start_row, start_col, dims)` of each tile that `tiles` would yield,
+    /// without constructing the views
+    pub fn tile_coords(&self, tile_dims: ImageDimensions) -> impl Iterator<Item = (usize, usize, ImageDimensions)> {
+        tile_grid(self.child_dims, tile_dims)
+    }
+
+    /// A zero-copy view of the transpose of this view:
+    /// local `(row, col)` of the result is `(col, row)` of this view
+    pub fn transpose(&self) -> SliceView<'a, T> {
         Self {
             passthru: false,
-            parent_dims,
-            child_dims,
-            parent_start_col: second_child_start_col,
-            parent_start_row,
-            slice
+            child_dims: ImageDimensions::new(self.child_dims.rows, self.child_dims.columns),
+            transposed: !self.transposed,
+            ..*self
+        }
+    }
+
+    /// A zero-copy view of this view with its rows in reverse order:
+    /// local row `r` of the result is row `child_dims.rows - 1 - r` of this view.
+    /// Suits bottom-up bitmap formats such as BMP, read in top-down order.
+    pub fn flip_rows(&self) -> SliceView<'a, T> {
+        let mut flipped = Self { passthru: false, ..*self };
+        if self.transposed {
+            flipped.flip_window_cols = !self.flip_window_cols;
+        }
+        else {
+            flipped.flip_window_rows = !self.flip_window_rows;
+        }
+        flipped
+    }
+
+    /// A zero-copy view of this view with its columns in reverse order:
+    /// local column `c` of the result is column `child_dims.columns - 1 - c` of this view
+    pub fn flip_cols(&self) -> SliceView<'a, T> {
+        let mut flipped = Self { passthru: false, ..*self };
+        if self.transposed {
+            flipped.flip_window_rows = !self.flip_window_rows;
+        }
+        else {
+            flipped.flip_window_cols = !self.flip_window_cols;
+        }
+        flipped
+    }
+
+    /// A zero-copy view of this view rotated 90 degrees clockwise, with swapped dimensions:
+    /// local `(row, col)` of the result is `(child_dims.rows - 1 - col, row)` of this view.
+    /// The rotation stays within the view's window of the parent.
+    pub fn rotate90_cw(&self) -> SliceView<'a, T> {
+        self.transpose().flip_cols()
+    }
+
+    /// A zero-copy view of this view rotated 180 degrees
+    pub fn rotate180(&self) -> SliceView<'a, T> {
+        self.flip_rows().flip_cols()
+    }
+
+    /// A zero-copy view of this view rotated 270 degrees clockwise, with swapped dimensions:
+    /// local `(row, col)` of the result is `(col, child_dims.columns - 1 - row)` of this view
+    pub fn rotate270_cw(&self) -> SliceView<'a, T> {
+        self.transpose().flip_rows()
+    }
+
+    /// Translate a local index within this view into an index in the parent slice.
+    /// Panics if the view is empty, having no pixels to translate.
+    pub fn local_index_to_parent_index(&self, idx: usize) -> usize {
+        assert!(!self.is_empty(), "index {} into an empty view", idx);
+        if self.passthru {
+            idx
+        }
+        else {
+            let (row, col) = self.parent_coords(idx);
+            row * self.parent_stride + col * self.pixel_stride
+        }
+    }
+
+    /// Number of elements between the starts of adjacent parent rows
+    pub fn parent_stride(&self) -> usize {
+        self.parent_stride
+    }
+
+    /// Index in the parent slice of the top-left pixel of the view's window
+    pub fn backing_offset(&self) -> usize {
+        self.parent_start_row * self.parent_stride + self.parent_start_col * self.pixel_stride
+    }
+
+    /// The parent slice from the view's top-left pixel onwards,
+    /// for APIs that take a base pointer plus `parent_stride`.
+    /// Empty if the origin lies beyond the end of the parent slice.
+    pub fn backing_slice_from_origin(&self) -> &'a [T] {
+        self.slice.get(self.backing_offset()..).unwrap_or(&[])
+    }
+
+    /// Number of elements in the parent slice
+    pub fn backing_len(&self) -> usize {
+        self.slice.len()
+    }
+
+    /// Fraction of the parent slice that the view's pixels logically cover,
+    /// `len() / backing_len()`, as a diagnostic for misconfigured views:
+    /// tiny for a small window over a huge buffer, and above 1.0 for a view
+    /// that claims more pixels than the buffer holds. Returns 0.0 over an
+    /// empty backing slice, where the fraction is undefined
+    pub fn utilization(&self) -> f32 {
+        if self.backing_len() == 0 {
+            return 0.0;
+        }
+        self.len() as f32 / self.backing_len() as f32
+    }
+
+    /// Index in the parent slice of the bottom-right element of the view's window,
+    /// the furthest into the slice whatever the orientation of the view,
+    /// or `None` for an empty view
+    fn last_parent_index(&self) -> Option<usize> {
+        if self.is_empty() {
+            return None;
+        }
+        let (rows, cols) = self.window_extent();
+        Some((self.parent_start_row + rows - 1) * self.parent_stride
+            + (self.parent_start_col + cols - 1) * self.pixel_stride)
+    }
+
+    /// Byte addresses of the first and last element of the parent slice
+    /// that the view's window spans, or `None` for an empty view
+    fn address_span(&self) -> Option<(usize, usize)> {
+        let last = self.last_parent_index()?;
+        let base = self.slice.as_ptr() as usize;
+        let size = core::mem::size_of::<T>();
+        Some((base + self.backing_offset() * size, base + last * size + size.max(1) - 1))
+    }
+
+    /// Whether this view and `other` may share pixels, as a precondition check
+    /// before aliasing-sensitive operations on several views of one buffer.
+    /// Views of the same parent slice overlap if their windows intersect;
+    /// otherwise this is conservative, and returns `true` unless the spans of
+    /// memory behind the two views are disjoint.
+    pub fn overlaps(&self, other: &SliceView<T>) -> bool {
+        let (Some((start, end)), Some((other_start, other_end))) = (self.address_span(), other.address_span())
+        else {
+            return false;
+        };
+        if end < other_start || other_end < start {
+            return false;
+        }
+        let same_layout = self.slice.as_ptr() == other.slice.as_ptr()
+            && self.parent_stride == other.parent_stride
+            && self.pixel_stride == other.pixel_stride;
+        let (rows, cols) = self.window_extent();
+        let (other_rows, other_cols) = other.window_extent();
+        // with rows that wrap onto the next, windows may share pixels without intersecting
+        let fits_stride = |start_col: usize, cols: usize| (start_col + cols - 1) * self.pixel_stride < self.parent_stride;
+        if !same_layout || !fits_stride(self.parent_start_col, cols) || !fits_stride(other.parent_start_col, other_cols) {
+            return true;
+        }
+        self.parent_start_row < other.parent_start_row + other_rows
+            && other.parent_start_row < self.parent_start_row + rows
+            && self.parent_start_col < other.parent_start_col + other_cols
+            && other.parent_start_col < self.parent_start_col + cols
+    }
+}
+
+/// An empty view of an empty slice
+impl<'a, T> Default for SliceView<'a, T> {
+    fn default() -> Self {
+        Self::new_passthru(ImageDimensions::default(), &[])
+    }
+}
+
+/// Wrap a whole image as a passthru view
+impl<'a, T> From<(&'a [T], ImageDimensions)> for SliceView<'a, T> {
+    fn from((slice, dims): (&'a [T], ImageDimensions)) -> Self {
+        debug_assert!(slice.len() >= dims.columns * dims.rows, "slice too short for dims");
+        Self::new_passthru(dims, slice)
+    }
+}
+
+// Not derived, since that would require `T: Clone`
+impl<'a, T> Clone for SliceView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for SliceView<'a, T> {}
+
+impl<'a, T> IntoIterator for &SliceView<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Views are equal when they have the same dimensions and pixel values,
+/// regardless of which parent or position they view
+impl<'a, 'b, T: PartialEq> PartialEq<SliceView<'b, T>> for SliceView<'a, T> {
+    fn eq(&self, other: &SliceView<'b, T>) -> bool {
+        self.child_dims == other.child_dims && self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, T: Eq> Eq for SliceView<'a, T> {}
+
+/// Hashes the dimensions and pixel values, consistent with `PartialEq`
+impl<'a, T: Hash> Hash for SliceView<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.child_dims.hash(state);
+        for px in self.iter() {
+            px.hash(state);
         }
-        )
     }
 }
 
 impl<'a, T> Index<usize> for SliceView<'a, T> {
     type Output = T;
 
+    /// Panics if the view is empty. Otherwise, an index beyond the end of the view
+    /// reads from the following rows of the parent, so long as it is within the parent slice.
     fn index(&self, idx: usize) -> &T {
-        let frame_idx = if !self.passthru {
-            let child_y = idx / self.child_dims.columns;
-            let child_x = idx % self.child_dims.columns;
-            let frame_x = self.parent_start_col + child_x;
-            let frame_y = self.parent_start_row + child_y;
-            frame_y * self.parent_dims.columns + frame_x
+        assert!(!self.is_empty(), "index {} into an empty view", idx);
+        #[cfg(feature = "debug-checks")]
+        {
+            debug_assert!(idx < self.len(), "index {} outside view of {} pixels", idx, self.len());
+            let (row, col) = self.parent_coords(idx);
+            debug_assert!(
+                row < self.parent_dims.rows && col < self.parent_dims.columns,
+                "index {} outside parent image", idx
+            );
         }
-        else { idx };
-        &self.slice[frame_idx]
+        &self.slice[self.local_index_to_parent_index(idx)]
     }
 }
 
+impl<'a, T> Index<(usize, usize)> for SliceView<'a, T> {
+    type Output = T;
+
+    /// The pixel at local `(row, col)`. Panics if this lies outside the view.
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        match self.checked_row_major_index(row, col) {
+            Some(idx) => &self[idx],
+            None => panic!("({}, {}) is outside view of {:?}", row, col, self.child_dims),
+        }
+    }
+}
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
-    const FRAME_64_DIM: usize = 8;
-    const FRAME_64: [u8; FRAME_64_DIM * FRAME_64_DIM] = [
+    pub(crate) const FRAME_64_DIM: usize = 8;
+    pub(crate) const FRAME_64: [u8; FRAME_64_DIM * FRAME_64_DIM] = [
         10, 20, 30, 40, 50, 60, 70, 80,
         11, 21, 31, 41, 51, 61, 71, 81,
         12, 22, 32, 42, 52, 62, 72, 82,
@@ -131,6 +1026,7 @@ mod tests {
         assert_eq!(view[CHILD_COLS*CHILD_ROWS - 1], 52); // bottom-right of child: 52
     }
 
+    #[cfg(not(feature = "debug-checks"))]
     #[test]
     fn overwrap() {
         let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
@@ -149,6 +1045,46 @@ mod tests {
         assert_eq!(view[CHILD_COLS*CHILD_ROWS - 1], 23); // bottom-right of child: 23
     }
 
+    #[cfg(not(feature = "debug-checks"))]
+    #[test]
+    fn strict_overwrap() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let child = ImageDimensions::new(3,3);
+        let view = SliceView::new(parent, 0, 7, &FRAME_64, child);
+
+        assert_eq!(view.get_strict(0), Some(&80));
+        assert_eq!(view.get_strict(3), Some(&81));
+        // local (0,1) is beyond the right edge of the parent
+        assert_eq!(view[1], 11);
+        assert_eq!(view.get(1), Some(&11));
+        assert_eq!(view.get_strict(1), None);
+        assert_eq!(view.get(9), None);
+        assert_eq!(view.get_strict(9), None);
+        assert_eq!(*view.index_strict(6), 82);
+    }
+
+    #[cfg(not(feature = "debug-checks"))]
+    #[test]
+    fn default_for_overwrap() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 0, 7, &FRAME_64, ImageDimensions::new(3,3));
+        assert_eq!(*view.get_or(0, &0), 80);
+        // local (0,1) would wrap around to 11
+        assert_eq!(*view.get_or(1, &0), 0);
+        assert_eq!(*view.get_or(9, &0), 0);
+        assert_eq!(*view.get_or_at(2, 0, &0), 82);
+        assert_eq!(*view.get_or_at(2, 1, &0), 0);
+        assert_eq!(*view.get_or_at(0, 3, &0), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_strict_overwrap() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 0, 7, &FRAME_64, ImageDimensions::new(3,3));
+        let _ = view.index_strict(1);
+    }
+
     #[test]
     fn split_view() {
         let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
@@ -177,4 +1113,762 @@ mod tests {
         let max_idx = FRAME_64_DIM*FRAME_64_DIM - 1;
         assert_eq!(view[max_idx], FRAME_64[max_idx]);
     }
+
+    #[test]
+    fn from_slice_and_dims() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view: SliceView<_> = (&FRAME_64[..], parent).into();
+        let passthru = SliceView::new_passthru(parent, &FRAME_64);
+        for idx in 0..view.len() {
+            assert_eq!(view[idx], passthru[idx]);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "into an empty view")]
+    fn translate_zero_width() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(0,3));
+        let _ = view.local_index_to_parent_index(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "too short")]
+    fn from_short_slice() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let _ = SliceView::from((&FRAME_64[1..], parent));
+    }
+
+    #[test]
+    fn iter_view() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let child = ImageDimensions::new(3,2);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, child);
+        assert_eq!(view.len(), 6);
+        let mut it = view.iter();
+        assert_eq!(it.len(), 6);
+        assert_eq!(it.next(), Some(&31));
+        assert_eq!(it.next_back(), Some(&52));
+        assert!(it.eq([41, 51, 32, 42].iter()));
+        let mut sum = 0u32;
+        for px in &view {
+            sum += *px as u32;
+        }
+        assert_eq!(sum, 31 + 41 + 51 + 32 + 42 + 52);
+    }
+
+    #[test]
+    fn content_eq_and_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        fn hash_of<H: Hash>(val: &H) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            val.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let child = ImageDimensions::new(2,2);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, child);
+
+        let copy = [31u8, 41, 32, 42];
+        let copy_view = SliceView::new_passthru(child, &copy);
+        assert!(view == copy_view);
+        assert_eq!(hash_of(&view), hash_of(&copy_view));
+
+        let other = SliceView::new(parent, 1, 3, &FRAME_64, child);
+        assert!(view != other);
+        // same pixels, different shape
+        let flat = SliceView::new_passthru(ImageDimensions::new(4,1), &copy);
+        assert!(view != flat);
+    }
+
+    #[test]
+    fn default_empty() {
+        let view: SliceView<u8> = SliceView::default();
+        assert_eq!(view.len(), 0);
+        assert!(view.is_empty());
+        assert!(view.is_valid());
+        assert_eq!(view.iter().next(), None);
+
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let child = ImageDimensions::new(3,3);
+        assert!(SliceView::new(parent, 5, 5, &FRAME_64, child).is_valid());
+        // the bottom row runs off the end of the slice
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(!SliceView::new(parent, 6, 5, &FRAME_64, child).is_valid());
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(!SliceView::new_passthru(parent, &FRAME_64[1..]).is_valid());
+    }
+
+    #[cfg(feature = "debug-checks")]
+    #[test]
+    #[should_panic(expected = "view window does not fit within parent")]
+    fn debug_check_overwrap() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let _ = SliceView::new(parent, 0, 7, &FRAME_64, ImageDimensions::new(3,3));
+    }
+
+    #[cfg(feature = "debug-checks")]
+    #[test]
+    #[should_panic(expected = "view window does not fit within parent")]
+    fn debug_check_short_slice() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let _ = SliceView::new_passthru(parent, &FRAME_64[1..]);
+    }
+
+    #[cfg(feature = "debug-checks")]
+    #[test]
+    #[should_panic(expected = "outside view")]
+    fn debug_check_index_beyond_view() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 1, &FRAME_64, ImageDimensions::new(2,2));
+        let _ = view[4];
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn split_cols_strips() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let strips = SliceView::try_split_cols(parent, 0, 0, &FRAME_64, FRAME_64_DIM, 4, 2).unwrap();
+        assert_eq!(strips.len(), 4);
+        for (i, strip) in strips.iter().enumerate() {
+            assert_eq!(strip.child_dims, ImageDimensions::new(2, FRAME_64_DIM));
+            assert_eq!(strip[0], FRAME_64[2 * i]);
+            assert_eq!(strip[2 * (FRAME_64_DIM - 1)], FRAME_64[(FRAME_64_DIM - 1) * FRAME_64_DIM + 2 * i]);
+        }
+        assert_eq!(
+            SliceView::try_split_cols(parent, 0, 1, &FRAME_64, FRAME_64_DIM, 4, 2).err(),
+            Some(SliceViewError::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn split_cols_array() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let strips: [SliceView<u8>; 4] = SliceView::try_split_cols_array(parent, 2, 0, &FRAME_64, 3, 2).unwrap();
+        let leftmost: Vec<u8> = strips.iter().map(|strip| strip[0]).collect();
+        assert_eq!(leftmost, [12, 32, 52, 72]);
+        assert!(SliceView::try_split_cols_array::<3>(parent, 6, 0, &FRAME_64, 3, 2).is_err());
+        assert!(SliceView::try_split_cols_array::<2>(parent, 0, 0, &FRAME_64[..19], 3, 2).is_err());
+    }
+
+    #[test]
+    fn zero_dimension_views() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        for &dims in &[ImageDimensions::new(0,3), ImageDimensions::new(3,0), ImageDimensions::new(0,0)] {
+            let view = SliceView::new(parent, 1, 1, &FRAME_64, dims);
+            assert_eq!(view.len(), 0);
+            assert!(view.is_empty());
+            assert!(view.is_valid());
+            assert_eq!(view.get(0), None);
+            assert_eq!(view.get_strict(0), None);
+            assert_eq!(view.iter().count(), 0);
+            assert_eq!(view.iter_col_major().count(), 0);
+            assert_eq!(view.parent_indexed().count(), 0);
+            assert_eq!(view.rows().count(), 0);
+            assert_eq!(view.enumerate_rows().count(), 0);
+            assert_eq!(view.tiles(ImageDimensions::new(2,2)).count(), 0);
+            assert_eq!(view.transpose().iter().count(), 0);
+            assert_eq!(view.fold(7, |acc, _| acc + 1), 7);
+            assert_eq!(view.mean(), 0.0);
+            assert_eq!(view.variance(), 0.0);
+            assert_eq!(view.crc32(), 0);
+            assert_eq!(view.find_value(31), None);
+            assert!(view == SliceView::new(parent, 5, 5, &FRAME_64, dims));
+
+            let mut frame = FRAME_64;
+            let mut view_mut = SliceViewMut::new(parent, 1, 1, &mut frame, dims);
+            assert_eq!(view_mut.rows_mut().count(), 0);
+            assert_eq!(view_mut.iter_mut().count(), 0);
+            view_mut.fill_checkerboard(0, 1, 1);
+            assert_eq!(frame, FRAME_64);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "empty view")]
+    fn index_zero_width_view() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 1, &FRAME_64, ImageDimensions::new(0,3));
+        let _ = view[0];
+    }
+
+    #[test]
+    fn move_origin() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let child = ImageDimensions::new(2,2);
+        let mut view = SliceView::new(parent, 0, 0, &FRAME_64, child);
+        assert_eq!(view[0], 10);
+        view.set_origin(3, 4);
+        assert_eq!(view[0], 53);
+        assert_eq!(view[3], 64);
+
+        let view = SliceView::new_passthru(parent, &FRAME_64).with_origin(1, 1);
+        assert_eq!(view[0], 21);
+        assert_eq!(view[FRAME_64_DIM], 22);
+    }
+
+    #[test]
+    fn col_major_matches_transpose() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let child = ImageDimensions::new(3,2);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, child);
+        let transposed = view.transpose();
+        assert_eq!(transposed.child_dims, ImageDimensions::new(2,3));
+        assert!(view.iter_col_major().eq(transposed.iter()));
+        let expected: [u8; 6] = [31, 32, 41, 42, 51, 52];
+        assert!(view.iter_col_major().eq(expected.iter()));
+        assert!(transposed.transpose() == view);
+    }
+
+    #[test]
+    fn tiles_view() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 1, &FRAME_64, ImageDimensions::new(5,3));
+        let tiles: Vec<_> = view.tiles(ImageDimensions::new(2,2)).collect();
+        assert_eq!(tiles.len(), 6);
+        assert_eq!(tiles[0][0], 21);
+        assert_eq!(tiles[2].child_dims, ImageDimensions::new(1,2));
+        assert_eq!(tiles[2][1], 62);
+        assert_eq!(tiles[4].child_dims, ImageDimensions::new(2,1));
+        assert_eq!(tiles[4][1], 53);
+
+        let transposed = view.transpose();
+        let sub = transposed.sub_view(1, 0, ImageDimensions::new(3,2));
+        assert!(sub == view.sub_view(0, 1, ImageDimensions::new(2,3)).transpose());
+    }
+
+    #[test]
+    fn override_stride() {
+        let parent = ImageDimensions::new(6,8);
+        let child = ImageDimensions::new(2,2);
+        let view = SliceView::new(parent, 1, 1, &FRAME_64, child);
+        assert_eq!(view[2], FRAME_64[2 * 6 + 1]);
+        // the same buffer, actually laid out with rows of 8
+        let view = view.with_parent_stride(FRAME_64_DIM);
+        assert_eq!(view[0], 21);
+        assert_eq!(view[2], 22);
+
+        let view = SliceView::new_passthru(parent, &FRAME_64).with_parent_stride(FRAME_64_DIM);
+        assert_eq!(view[6], 11);
+    }
+
+    #[test]
+    #[should_panic]
+    fn stride_too_narrow() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let _ = SliceView::new_passthru(parent, &FRAME_64).with_parent_stride(4);
+    }
+
+    #[test]
+    fn tile_coords_uneven() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 1, &FRAME_64, ImageDimensions::new(5,3));
+        let coords: Vec<_> = view.tile_coords(ImageDimensions::new(2,2)).collect();
+        assert_eq!(coords, [
+            (0, 0, ImageDimensions::new(2,2)),
+            (0, 2, ImageDimensions::new(2,2)),
+            (0, 4, ImageDimensions::new(1,2)),
+            (2, 0, ImageDimensions::new(2,1)),
+            (2, 2, ImageDimensions::new(2,1)),
+            (2, 4, ImageDimensions::new(1,1)),
+        ]);
+    }
+
+    #[cfg(not(feature = "debug-checks"))]
+    #[test]
+    fn enumerate_contiguous_rows() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3,2));
+        let rows: Vec<(usize, &[u8])> = view.enumerate_rows().collect();
+        assert_eq!(rows, [(0, &[31u8, 41, 51][..]), (1, &[32, 42, 52][..])]);
+        assert!(view.rows().zip(rows.iter()).all(|(sub, (_, line))| sub.iter().eq(line.iter())));
+
+        let overwrap = SliceView::new(parent, 0, 7, &FRAME_64, ImageDimensions::new(3,3));
+        assert_eq!(overwrap.enumerate_rows().count(), 0);
+        assert_eq!(overwrap.rows().count(), 3);
+        assert_eq!(view.transpose().enumerate_rows().count(), 0);
+    }
+
+    #[cfg(not(feature = "debug-checks"))]
+    #[test]
+    fn row_callbacks() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3,2));
+        let mut collected = Vec::new();
+        let mut order = Vec::new();
+        view.for_each_row(|row, line| {
+            order.push(row);
+            collected.extend_from_slice(line);
+        }).unwrap();
+        assert_eq!(order, [0, 1]);
+        assert!(view.iter().eq(collected.iter()));
+
+        // overrunning rows are clipped at the parent edge
+        let overwrap = SliceView::new(parent, 0, 6, &FRAME_64, ImageDimensions::new(3,2));
+        let mut lines = Vec::new();
+        overwrap.for_each_row(|_, line| lines.push(line.to_vec())).unwrap();
+        assert_eq!(lines, [vec![70, 80], vec![71, 81]]);
+
+        assert_eq!(view.transpose().for_each_row(|_, _| {}), Err(SliceViewError::NotContiguous));
+        let short = SliceView::new(parent, 6, 0, &FRAME_64[..60], ImageDimensions::new(8,2));
+        assert_eq!(short.for_each_row(|_, _| panic!("called")), Err(SliceViewError::OutOfBounds));
+    }
+
+    #[test]
+    fn dimension_helpers() {
+        assert_eq!(ImageDimensions::new(640,480).aspect_ratio(), 4.0 / 3.0);
+        assert_eq!(ImageDimensions::new(8,8).aspect_ratio(), 1.0);
+        assert_eq!(ImageDimensions::new(2,8).aspect_ratio(), 0.25);
+
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        assert!(ImageDimensions::new(3,2).fits_within(&parent));
+        assert!(parent.fits_within(&parent));
+        assert!(!ImageDimensions::new(9,1).fits_within(&parent));
+        assert!(!ImageDimensions::new(1,9).fits_within(&parent));
+    }
+
+    #[cfg(not(feature = "debug-checks"))]
+    #[test]
+    fn truncated_frame_prefix() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 5, 2, &FRAME_64[..50], ImageDimensions::new(3,3));
+        // the slice ends just before column 2 of parent row 6, the view's second row
+        assert_eq!(view.len(), 9);
+        assert_eq!(view.accessible_len(), 3);
+        assert!(view.iter().take(view.accessible_len()).eq([35, 45, 55].iter()));
+        assert_eq!(view.iter_accessible().count(), view.accessible_len());
+        assert!(view.iter_accessible().eq([35, 45, 55].iter()));
+        assert_eq!(SliceView::new(parent, 5, 2, &FRAME_64, ImageDimensions::new(3,3)).accessible_len(), 9);
+
+        // the whole of the window counts, however the view is oriented
+        let reoriented = [
+            view.flip_rows(), view.flip_cols(), view.transpose(),
+            view.rotate90_cw(), view.rotate180(), view.rotate270_cw(),
+        ];
+        for turned in &reoriented {
+            assert!(!turned.is_valid());
+            assert_eq!(turned.accessible_len(), 3);
+            let mut accessible: Vec<_> = turned.iter_accessible().copied().collect();
+            accessible.sort();
+            assert_eq!(accessible, [35, 45, 55]);
+        }
+    }
+
+    #[cfg(not(feature = "debug-checks"))]
+    #[test]
+    fn reoriented_validity() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        // the bottom row of the window runs off the end of the slice
+        let view = SliceView::new(parent, 6, 5, &FRAME_64, ImageDimensions::new(3,3));
+        assert!(!view.is_valid());
+        assert!(!view.flip_rows().is_valid());
+        assert!(!view.flip_cols().is_valid());
+        assert!(!view.transpose().is_valid());
+        assert!(!view.rotate90_cw().is_valid());
+        assert!(!view.rotate180().is_valid());
+        assert!(!view.rotate270_cw().is_valid());
+        assert_eq!(view.flip_rows().accessible_len(), 6);
+        assert_eq!(view.rotate90_cw().iter_accessible().count(), 6);
+
+        let fits = SliceView::new(parent, 5, 5, &FRAME_64, ImageDimensions::new(3,3));
+        assert!(fits.flip_rows().is_valid() && fits.rotate270_cw().is_valid());
+        assert_eq!(fits.flip_cols().accessible_len(), 9);
+    }
+
+    #[test]
+    fn capped_dims() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(5,5));
+        let capped = view.clamp_dims(ImageDimensions::new(3,3));
+        assert_eq!(capped.child_dims, ImageDimensions::new(3,3));
+        assert_eq!(capped[0], view[0]);
+        assert_eq!(capped[8], 53);
+        assert_eq!(view.clamp_dims(ImageDimensions::new(9,2)).child_dims, ImageDimensions::new(5,2));
+    }
+
+    #[test]
+    fn sprite_grid() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new_passthru(parent, &FRAME_64);
+        let cells = view.grid::<2, 2>();
+        assert_eq!(cells[0][0][0], 10);
+        assert_eq!(cells[0][1][0], 50);
+        assert_eq!(cells[1][0][0], 14);
+        assert_eq!(cells[1][1][0], 54);
+        assert!(cells.iter().flatten().all(|cell| cell.child_dims == ImageDimensions::new(4,4)));
+        assert_eq!(cells[1][1][15], 87);
+    }
+
+    #[test]
+    #[should_panic]
+    fn uneven_grid() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let _ = SliceView::new_passthru(parent, &FRAME_64).grid::<3, 2>();
+    }
+
+    #[test]
+    fn fixed_size_blocks() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new_passthru(parent, &FRAME_64);
+        let blocks: Vec<[[u8; 2]; 2]> = view.blocks().collect();
+        assert_eq!(blocks.len(), 16);
+        assert_eq!(blocks[0], [[10, 20], [11, 21]]);
+        assert_eq!(blocks[5], [[32, 42], [33, 43]]);
+
+        // only whole blocks are yielded
+        assert_eq!(view.blocks::<3, 3>().count(), 4);
+    }
+
+    #[test]
+    fn crop_boxes() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 1, &FRAME_64, ImageDimensions::new(6,6));
+        let boxes = [
+            (0, 0, ImageDimensions::new(2,2)),
+            (4, 5, ImageDimensions::new(2,2)),
+            (3, 2, ImageDimensions::new(1,3)),
+            (5, 5, ImageDimensions::new(1,1)),
+        ];
+        let crops: Vec<_> = view.rois(&boxes).collect();
+        // the second box overruns the right edge of the view
+        assert_eq!(crops.len(), 3);
+        assert!(crops[0].iter().eq([21, 31, 22, 32].iter()));
+        assert!(crops[1].iter().eq([44, 45, 46].iter()));
+        assert_eq!(crops[2][0], 76);
+    }
+
+    #[test]
+    fn scan_orders() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3,5));
+        let sorted = |order: ScanOrder| {
+            let mut visited: Vec<_> = view.iter_order(order).collect();
+            visited.sort();
+            visited
+        };
+        let row_major: Vec<_> = view.iter_order(ScanOrder::RowMajor).collect();
+        assert_eq!(row_major.len(), 15);
+        assert!(row_major.iter().map(|&(_, _, px)| px).eq(view.iter()));
+        for &order in &[ScanOrder::ColumnMajor, ScanOrder::BoustrophedonRows, ScanOrder::Morton] {
+            assert_eq!(sorted(order), row_major, "{:?}", order);
+        }
+        assert!(view.iter_order(ScanOrder::ColumnMajor).map(|(_, _, px)| px).eq(view.iter_col_major()));
+
+        let serpentine: Vec<_> = view.iter_order(ScanOrder::BoustrophedonRows).map(|(_, _, &px)| px).collect();
+        assert_eq!(serpentine[..9], [31, 41, 51, 52, 42, 32, 33, 43, 53]);
+
+        let morton: Vec<_> = view.iter_order(ScanOrder::Morton).map(|(row, col, _)| (row, col)).collect();
+        assert_eq!(morton[..6], [(0, 0), (0, 1), (1, 0), (1, 1), (0, 2), (1, 2)]);
+        assert_eq!(SliceView::<u8>::default().iter_order(ScanOrder::Morton).count(), 0);
+
+        // walks only the blocks of the curve that meet a long strip
+        let strip = [0u8; 1 << 14];
+        let dims = ImageDimensions::new(strip.len(), 1);
+        let view = SliceView::new_passthru(dims, &strip);
+        assert!(view.iter_order(ScanOrder::Morton).map(|(_, col, _)| col).eq(0..strip.len()));
+        let column = view.transpose();
+        assert!(column.iter_order(ScanOrder::Morton).map(|(row, _, _)| row).eq(0..strip.len()));
+    }
+
+    #[test]
+    fn cursor_seek() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3,4));
+        let mut cursor = view.cursor();
+        assert!(view.cursor().eq(view.iter()));
+        assert_eq!(cursor.current_coords(), (0, 0));
+        cursor.seek(2, 1);
+        assert_eq!(cursor.next(), Some(&view[(2, 1)]));
+        assert_eq!(cursor.next(), Some(&view[(2, 2)]));
+        assert_eq!(cursor.current_coords(), (3, 0));
+        assert_eq!(cursor.next(), Some(&view[(3, 0)]));
+        cursor.seek(0, 2);
+        assert_eq!(cursor.next(), Some(&51));
+        cursor.seek(3, 2);
+        assert_eq!(cursor.next(), Some(&54));
+        assert_eq!(cursor.next(), None);
+
+        // steps through reoriented views too
+        let rotated = view.rotate90_cw();
+        assert!(rotated.cursor().eq(rotated.iter()));
+        let mut cursor = rotated.cursor();
+        cursor.seek(1, 2);
+        assert!(cursor.eq(rotated.iter().skip(6)));
+        let column = view.sub_view(0, 1, ImageDimensions::new(1,4));
+        assert!(column.cursor().eq(column.iter()));
+        assert_eq!(SliceView::<u8>::default().cursor().next(), None);
+        let zero_width = view.sub_view(0, 0, ImageDimensions::new(0,4));
+        assert_eq!(zero_width.cursor().next(), None);
+    }
+
+    #[test]
+    fn row_major_indexing() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3,4));
+        for row in 0..4 {
+            for col in 0..3 {
+                assert_eq!(view[view.row_major_index(row, col)], view[(row, col)]);
+                assert_eq!(view.checked_row_major_index(row, col), Some(row * 3 + col));
+            }
+        }
+        assert_eq!(view[(3, 2)], 54);
+        assert_eq!(view.transpose()[(2, 3)], 54);
+        assert_eq!(view.checked_row_major_index(0, 3), None);
+        assert_eq!(view.checked_row_major_index(4, 0), None);
+        assert_eq!(view.row_major_index(0, 3), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_beyond_row() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3,4));
+        let _ = view[(0, 3)];
+    }
+
+    #[test]
+    fn gather_corners() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3,4));
+        assert_eq!(view.get_many([0, 2, 9, 11]), [Some(&31), Some(&51), Some(&34), Some(&54)]);
+        assert_eq!(view.get_many([1, 12]), [Some(&41), None]);
+    }
+
+    #[test]
+    fn bottom_up_rows() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3,4));
+        let flipped = view.flip_rows();
+        assert_eq!(flipped.child_dims, view.child_dims);
+        assert_eq!(flipped.row_slice(0), view.row_slice(3));
+        assert_eq!(flipped[0], 34);
+        assert_eq!(flipped[5], 53);
+        assert!(flipped.flip_rows() == view);
+
+        let sub = flipped.sub_view(1, 1, ImageDimensions::new(2,2));
+        assert!(sub.iter().eq([43, 53, 42, 52].iter()));
+        // clipped where it would read past the top of the window
+        let sub = flipped.sub_view(2, 0, ImageDimensions::new(3,4));
+        assert_eq!(sub.child_dims, ImageDimensions::new(3,2));
+        assert!(sub.iter().eq([32, 42, 52, 31, 41, 51].iter()));
+        assert!(flipped.sub_view(5, 0, ImageDimensions::new(3,1)).is_empty());
+        let sub = view.flip_cols().transpose().sub_view(1, 1, ImageDimensions::new(1,3));
+        assert_eq!(sub.child_dims, ImageDimensions::new(1,2));
+        assert!(sub.iter().eq([42, 32].iter()));
+        let mut lines = Vec::new();
+        flipped.for_each_row(|_, line| lines.push(line[0])).unwrap();
+        assert_eq!(lines, [34, 33, 32, 31]);
+
+        // flipping the rows of a transposed view reverses the window's columns
+        let transposed = view.transpose().flip_rows();
+        assert_eq!(transposed[0], 51);
+        assert!(transposed.iter().eq(view.transpose().rows().collect::<Vec<_>>().iter().rev().flatten()));
+    }
+
+    #[test]
+    fn rotate_non_square_window() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3,2));
+        // 31 41 51
+        // 32 42 52
+        let cw = view.rotate90_cw();
+        assert_eq!(cw.child_dims, ImageDimensions::new(2,3));
+        assert!(cw.iter().eq([32, 31, 42, 41, 52, 51].iter()));
+        for row in 0..3 {
+            for col in 0..2 {
+                assert_eq!(cw.at(row, col), view.at(1 - col, row));
+            }
+        }
+        assert_eq!(*cw.at(0, 0), 32);
+        assert_eq!(*cw.at(0, 1), 31);
+        assert_eq!(*cw.at(2, 0), 52);
+        assert_eq!(*cw.at(2, 1), 51);
+
+        let ccw = view.rotate270_cw();
+        assert_eq!(ccw.child_dims, ImageDimensions::new(2,3));
+        assert!(ccw.iter().eq([51, 52, 41, 42, 31, 32].iter()));
+        assert!(view.rotate180().iter().eq([52, 42, 32, 51, 41, 31].iter()));
+        assert!(cw.rotate90_cw() == view.rotate180());
+        assert!(cw.rotate270_cw() == view);
+        assert!(cw.rotate90_cw().rotate90_cw().rotate90_cw() == view);
+
+        // the rotated view can itself be windowed
+        let sub = cw.sub_view(1, 0, ImageDimensions::new(2,2));
+        assert!(sub.iter().eq([42, 41, 52, 51].iter()));
+        assert_eq!(view.flip_cols()[0], 51);
+    }
+
+    #[test]
+    fn fit_window_to_parent() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let requested = ImageDimensions::new(5,5);
+        let view = SliceView::fit_from_origin(parent, 1, 5, requested, &FRAME_64);
+        assert_eq!(view.child_dims, ImageDimensions::new(3,5));
+        assert!(view.is_valid());
+        assert_eq!(view[0], 61);
+        assert_eq!(view[14], 85);
+
+        let corner = SliceView::fit_from_origin(parent, 6, 5, requested, &FRAME_64);
+        assert_eq!(corner.child_dims, ImageDimensions::new(3,2));
+        let inside = SliceView::fit_from_origin(parent, 0, 0, requested, &FRAME_64);
+        assert_eq!(inside.child_dims, requested);
+        assert!(SliceView::fit_from_origin(parent, 0, 9, requested, &FRAME_64).is_empty());
+    }
+
+    #[test]
+    fn checked_passthru() {
+        let dims = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::try_passthru(&FRAME_64, dims).unwrap();
+        assert_eq!(view.child_dims, dims);
+        let err = SliceView::try_passthru(&FRAME_64[..63], dims).err();
+        assert_eq!(err, Some(SliceViewError::WrongLength { expected: 64, actual: 63 }));
+        let err = SliceView::try_passthru(&FRAME_64, ImageDimensions::new(7,7)).err();
+        assert_eq!(err, Some(SliceViewError::WrongLength { expected: 49, actual: 64 }));
+    }
+
+    #[test]
+    fn every_other_element() {
+        // 4x3 image of pixels interleaved with a metadata element each
+        let frame: Vec<u8> = (0..24).collect();
+        let parent = ImageDimensions::new(4,3);
+        let view = SliceView::new_with_stride(parent, 8, 0, 0, &frame, parent).with_pixel_stride(2);
+        assert!(view.iter().eq([0, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22].iter()));
+
+        let metadata = SliceView::new_with_stride(parent, 8, 1, 1, &frame[1..], ImageDimensions::new(2,2))
+            .with_pixel_stride(2);
+        assert!(metadata.iter().eq([11, 13, 19, 21].iter()));
+        assert_eq!(metadata.backing_offset(), 10);
+        assert!(metadata.row_slice(0).is_none());
+        assert_eq!(metadata.transpose()[1], 19);
+    }
+
+    #[test]
+    fn view_of_2d_array() {
+        let arr = [[1u8, 2, 3], [4, 5, 6]];
+        let view = SliceView::from_array_2d(&arr);
+        assert_eq!(view.child_dims, ImageDimensions::new(3,2));
+        assert_eq!(view[1], 2);
+        assert_eq!(view[3], 4);
+        assert_eq!(*view.at(1, 2), 6);
+    }
+
+    #[test]
+    fn aligned_row_parts() {
+        let frame: Vec<u16> = (0..256).collect();
+        let parent = ImageDimensions::new(16,16);
+        let view = SliceView::new(parent, 2, 1, &frame, ImageDimensions::new(13,3));
+        for row in 0..3 {
+            let (head, middle, tail) = view.row_slice_aligned(row, 8).unwrap();
+            assert_eq!(middle.as_ptr() as usize % 8, 0);
+            assert_eq!(middle.len() % 4, 0);
+            assert!(head.len() < 4 && tail.len() < 4);
+            let joined: Vec<u16> = head.iter().chain(middle).chain(tail).copied().collect();
+            assert_eq!(joined, view.row_slice(row).unwrap());
+        }
+        assert!(view.transpose().row_slice_aligned(0, 8).is_none());
+    }
+
+    #[test]
+    fn split_at_blank_rows() {
+        let mut frame = FRAME_64;
+        frame[3 * FRAME_64_DIM..4 * FRAME_64_DIM].fill(0);
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 0, &frame, ImageDimensions::new(FRAME_64_DIM,5));
+        let bands: Vec<_> = view.split_rows_where(|row| row.iter().all(|&px| px == 0)).collect();
+        assert_eq!(bands.len(), 2);
+        assert_eq!(bands[0].child_dims, ImageDimensions::new(FRAME_64_DIM,2));
+        assert_eq!(bands[0][0], 11);
+        assert_eq!(bands[1].child_dims, ImageDimensions::new(FRAME_64_DIM,2));
+        assert_eq!(bands[1][0], 14);
+
+        assert_eq!(view.split_rows_where(|_| true).count(), 0);
+        assert_eq!(view.split_rows_where(|_| false).count(), 1);
+
+        // works on views whose rows are not contiguous, testing each row once
+        let mut tested = 0;
+        let flipped = view.flip_cols();
+        let bands: Vec<_> = flipped.split_rows_where(|row| {
+            tested += 1;
+            row.iter().all(|&px| px == 0)
+        }).collect();
+        assert_eq!(tested, 5);
+        assert_eq!(bands.len(), 2);
+        assert_eq!(bands[1][0], 84);
+        let transposed = view.transpose();
+        assert_eq!(transposed.split_rows_where(|row| row[0] == 0).count(), 1);
+    }
+
+    #[test]
+    fn backing_slice_offset() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 2, 3, &FRAME_64, ImageDimensions::new(3,2));
+        assert_eq!(view.backing_offset(), view.local_index_to_parent_index(0));
+        let backing = view.backing_slice_from_origin();
+        assert_eq!(backing[0], view[0]);
+        assert_eq!(backing[view.parent_stride()], view[3]);
+        assert_eq!(view.transpose().backing_offset(), view.backing_offset());
+        assert!(view.with_origin(9, 0).backing_slice_from_origin().is_empty());
+    }
+
+    #[test]
+    fn backing_utilization() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let view = SliceView::new(parent, 2, 3, &FRAME_64, ImageDimensions::new(4,2));
+        assert_eq!(view.backing_len(), 64);
+        assert_eq!(view.utilization(), 0.125);
+        assert_eq!(SliceView::new_passthru(parent, &FRAME_64).utilization(), 1.0);
+        assert_eq!(SliceView::<u8>::default().utilization(), 0.0);
+    }
+
+    #[cfg(not(feature = "debug-checks"))]
+    #[test]
+    fn over_declared_utilization() {
+        let claimed = SliceView::new_passthru(ImageDimensions::new(8,10), &FRAME_64);
+        assert_eq!(claimed.utilization(), 1.25);
+    }
+
+    #[test]
+    fn overlapping_windows() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let dims = ImageDimensions::new(3,2);
+        let view = SliceView::new(parent, 2, 2, &FRAME_64, dims);
+        let right = SliceView::new(parent, 2, 5, &FRAME_64, dims);
+        let below = SliceView::new(parent, 4, 2, &FRAME_64, dims);
+        assert!(!view.overlaps(&right));
+        assert!(!right.overlaps(&view));
+        assert!(!view.overlaps(&below));
+
+        let shifted = SliceView::new(parent, 3, 4, &FRAME_64, dims);
+        assert!(view.overlaps(&shifted));
+        assert!(shifted.overlaps(&view));
+        assert!(view.overlaps(&view.transpose()));
+
+        // differing layouts are assumed to overlap wherever their spans of memory do
+        let even = SliceView::new(parent, 2, 0, &FRAME_64, dims).with_pixel_stride(2);
+        assert!(view.overlaps(&even));
+        let even = SliceView::new(parent, 6, 0, &FRAME_64, dims).with_pixel_stride(2);
+        assert!(!view.overlaps(&even));
+
+        let other = FRAME_64;
+        assert!(!view.overlaps(&SliceView::new(parent, 2, 2, &other, dims)));
+        assert!(!view.overlaps(&SliceView::default()));
+    }
+
+    #[test]
+    fn parent_indexed_view() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let child = ImageDimensions::new(3,2);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, child);
+        let mut count = 0;
+        for (idx, (parent_idx, px)) in view.parent_indexed().enumerate() {
+            assert_eq!(parent_idx, view.local_index_to_parent_index(idx));
+            assert_eq!(*px, FRAME_64[parent_idx]);
+            count += 1;
+        }
+        assert_eq!(count, view.len());
+        let first_row: [usize; 3] = [10, 11, 12];
+        assert!(view.parent_indexed().map(|(i, _)| i).take(3).eq(first_row.iter().copied()));
+    }
 }
@@ -0,0 +1,84 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! Frame-to-frame motion detection
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{ImageDimensions, SliceView};
+
+/// Detects motion between successive frames of a fixed size, by counting the pixels
+/// that changed by more than a threshold since the previous frame
+pub struct MotionDetector {
+    dims: ImageDimensions,
+    /// Number of changed pixels above which a frame counts as motion
+    min_changed: usize,
+    previous: Option<Vec<u8>>,
+    mask: Vec<u8>,
+}
+
+impl MotionDetector {
+    /// A detector for frames of `dims`, reporting motion for frames in which
+    /// more than `min_changed` pixels changed
+    pub fn new(dims: ImageDimensions, min_changed: usize) -> Self {
+        let len = dims.columns * dims.rows;
+        Self { dims, min_changed, previous: None, mask: vec![0; len] }
+    }
+
+    /// Compare `frame` with the previous frame, updating the change mask
+    /// from pixels that differ by more than `threshold`, and remember it for next time.
+    /// Returns whether motion was detected, which it never is for the first frame.
+    /// Panics if `frame` is not of the detector's dimensions.
+    pub fn update(&mut self, frame: &SliceView<u8>, threshold: u8) -> bool {
+        assert_eq!(frame.child_dims, self.dims, "frame differs in dimensions");
+        let changed = match &self.previous {
+            Some(previous) => {
+                let previous = SliceView::new_passthru(self.dims, previous);
+                frame.diff_mask_into(&previous, threshold as u32, &mut self.mask);
+                self.mask.iter().filter(|&&px| px != 0).count()
+            }
+            None => 0,
+        };
+        let previous = self.previous.get_or_insert_with(Vec::new);
+        previous.clear();
+        previous.extend(frame.iter().copied());
+        changed > self.min_changed
+    }
+
+    /// Change mask computed by the last `update`, densely packed:
+    /// 255 where a pixel changed and 0 elsewhere
+    pub fn mask(&self) -> &[u8] {
+        &self.mask
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MotionDetector;
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+    use crate::{ImageDimensions, SliceView};
+
+    #[test]
+    fn detects_changed_frame() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let dims = ImageDimensions::new(4, 4);
+        let mut detector = MotionDetector::new(dims, 2);
+
+        let still = SliceView::new(parent, 2, 2, &FRAME_64, dims);
+        assert!(!detector.update(&still, 5));
+        assert!(!detector.update(&still, 5));
+        assert!(detector.mask().iter().all(|&px| px == 0));
+
+        let mut frame = FRAME_64;
+        for idx in [2 * FRAME_64_DIM + 3, 3 * FRAME_64_DIM + 3, 4 * FRAME_64_DIM + 4] {
+            frame[idx] = 0;
+        }
+        frame[5 * FRAME_64_DIM + 5] += 5; // within the threshold
+        let moved = SliceView::new(parent, 2, 2, &frame, dims);
+        assert!(detector.update(&moved, 5));
+        assert_eq!(detector.mask().iter().filter(|&&px| px == 255).count(), 3);
+        assert_eq!(detector.mask()[1], 255);
+    }
+}
@@ -0,0 +1,76 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! Netpbm (PGM/PPM) writers, for inspecting the region a view covers
+
+use std::io::{self, Write};
+
+use crate::SliceView;
+
+impl<'a> SliceView<'a, u8> {
+    /// Write the viewed pixels as a binary PGM (P5) grayscale image
+    pub fn write_pgm<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write!(w, "P5\n{} {}\n255\n", self.child_dims.columns, self.child_dims.rows)?;
+        for (row_idx, row) in self.rows().enumerate() {
+            match self.row_slice(row_idx) {
+                Some(line) => w.write_all(line)?,
+                None => {
+                    let line: std::vec::Vec<u8> = row.iter().copied().collect();
+                    w.write_all(&line)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> SliceView<'a, [u8; 3]> {
+    /// Write the viewed pixels as a binary PPM (P6) RGB image
+    pub fn write_ppm<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.child_dims.columns, self.child_dims.rows)?;
+        for px in self.iter() {
+            w.write_all(px)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+    use crate::{ImageDimensions, SliceView};
+
+    /// Split a binary Netpbm image into its magic number, dimensions and pixel data
+    fn parse_header(image: &[u8]) -> (&str, usize, usize, &[u8]) {
+        let mut fields = image.splitn(5, |&b| b == b'\n' || b == b' ');
+        let mut field = || std::str::from_utf8(fields.next().unwrap()).unwrap();
+        let magic = field();
+        let width = field().parse().unwrap();
+        let height = field().parse().unwrap();
+        assert_eq!(field(), "255");
+        (magic, width, height, fields.next().unwrap())
+    }
+
+    #[test]
+    fn pgm_of_window() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3, 2));
+        let mut out = Vec::new();
+        view.write_pgm(&mut out).unwrap();
+        let (magic, width, height, pixels) = parse_header(&out);
+        assert_eq!((magic, width, height), ("P5", 3, 2));
+        assert_eq!(pixels, [31, 41, 51, 32, 42, 52]);
+    }
+
+    #[test]
+    fn ppm_of_window() {
+        let frame: Vec<[u8; 3]> = (0..12u8).map(|i| [i, i + 100, i + 200]).collect();
+        let view = SliceView::new(ImageDimensions::new(4, 3), 1, 1, &frame, ImageDimensions::new(2, 1));
+        let mut out = Vec::new();
+        view.write_ppm(&mut out).unwrap();
+        let (magic, width, height, pixels) = parse_header(&out);
+        assert_eq!((magic, width, height), ("P6", 2, 1));
+        assert_eq!(pixels, [5, 105, 205, 6, 106, 206]);
+    }
+}
@@ -0,0 +1,267 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+use alloc::vec::Vec;
+use core::ops::Index;
+
+use crate::{ImageDimensions, Iter, SliceView, SliceViewError, SliceViewMut};
+
+/// A densely packed image that owns its pixels,
+/// typically copied out of a borrowed `SliceView`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OwnedSliceView<T> {
+    pub child_dims: ImageDimensions,
+    data: Vec<T>,
+}
+
+impl<T> OwnedSliceView<T> {
+    /// Wrap densely packed, row-major pixel data.
+    /// Fails with `WrongLength` unless `data` holds exactly one element per pixel.
+    pub fn from_vec(child_dims: ImageDimensions, data: Vec<T>) -> Result<Self, SliceViewError> {
+        let expected = child_dims.columns * child_dims.rows;
+        if data.len() != expected {
+            return Err(SliceViewError::WrongLength { expected, actual: data.len() });
+        }
+        Ok(Self { child_dims, data })
+    }
+
+    /// Borrow the owned pixels as a view
+    pub fn view(&self) -> SliceView<'_, T> {
+        SliceView::new_passthru(self.child_dims, &self.data)
+    }
+
+    /// Borrow the owned pixels as a mutable view
+    pub fn view_mut(&mut self) -> SliceViewMut<'_, T> {
+        SliceViewMut::new_passthru(self.child_dims, &mut self.data)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.view().iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.child_dims.columns * self.child_dims.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The densely packed pixels
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+}
+
+impl<T> Index<usize> for OwnedSliceView<T> {
+    type Output = T;
+
+    fn index(&self, idx: usize) -> &T {
+        &self.data[idx]
+    }
+}
+
+impl<'a, T: Clone> SliceView<'a, T> {
+    /// Copy the viewed region into a densely packed owned buffer
+    pub fn to_owned(&self) -> OwnedSliceView<T> {
+        OwnedSliceView { child_dims: self.child_dims, data: self.iter().cloned().collect() }
+    }
+
+    /// Copy the viewed region into nested `Vec`s, one per row,
+    /// for code that expects that layout
+    pub fn to_2d_vec(&self) -> Vec<Vec<T>> {
+        self.rows().map(|row| row.iter().cloned().collect()).collect()
+    }
+}
+
+/// A view that either borrows its pixels or owns a copy of them,
+/// cloning the borrowed pixels only when they are first modified
+pub enum CowSliceView<'a, T> {
+    Borrowed(SliceView<'a, T>),
+    Owned(OwnedSliceView<T>),
+}
+
+impl<'a, T> CowSliceView<'a, T> {
+    /// A view of the current pixels, whether borrowed or owned
+    pub fn view(&self) -> SliceView<'_, T> {
+        match self {
+            CowSliceView::Borrowed(view) => *view,
+            CowSliceView::Owned(owned) => owned.view(),
+        }
+    }
+
+    pub fn is_owned(&self) -> bool {
+        matches!(self, CowSliceView::Owned(_))
+    }
+}
+
+impl<'a, T: Clone> CowSliceView<'a, T> {
+    /// A mutable view of the pixels, first copying them if they are borrowed
+    pub fn to_mut(&mut self) -> SliceViewMut<'_, T> {
+        if let CowSliceView::Borrowed(view) = self {
+            *self = CowSliceView::Owned(view.to_owned());
+        }
+        match self {
+            CowSliceView::Owned(owned) => owned.view_mut(),
+            CowSliceView::Borrowed(_) => unreachable!(),
+        }
+    }
+
+    /// The owned pixels, copying them if they are borrowed
+    pub fn into_owned(self) -> OwnedSliceView<T> {
+        match self {
+            CowSliceView::Borrowed(view) => view.to_owned(),
+            CowSliceView::Owned(owned) => owned,
+        }
+    }
+}
+
+impl<'a, T> From<SliceView<'a, T>> for CowSliceView<'a, T> {
+    fn from(view: SliceView<'a, T>) -> Self {
+        CowSliceView::Borrowed(view)
+    }
+}
+
+impl<'a, T> From<OwnedSliceView<T>> for CowSliceView<'a, T> {
+    fn from(owned: OwnedSliceView<T>) -> Self {
+        CowSliceView::Owned(owned)
+    }
+}
+
+impl<'a, T: Copy> SliceView<'a, T> {
+    /// Copy the viewed region rotated 90 degrees clockwise into a densely packed `Vec`,
+    /// returning it along with its (swapped) dimensions
+    pub fn rotate90_cw_to_vec(&self) -> (Vec<T>, ImageDimensions) {
+        let cols = self.child_dims.columns;
+        let rows = self.child_dims.rows;
+        let out: Vec<T> = (0..cols)
+            .flat_map(|r| (0..rows).map(move |c| (r, c)))
+            .map(|(r, c)| self[(rows - 1 - c) * cols + r])
+            .collect();
+        (out, ImageDimensions::new(rows, cols))
+    }
+
+    /// Copy the viewed region rotated 180 degrees into a densely packed `Vec`
+    pub fn rotate180_to_vec(&self) -> (Vec<T>, ImageDimensions) {
+        (self.iter().rev().copied().collect(), self.child_dims)
+    }
+
+    /// Copy the viewed region rotated 270 degrees clockwise into a densely packed `Vec`,
+    /// returning it along with its (swapped) dimensions
+    pub fn rotate270_cw_to_vec(&self) -> (Vec<T>, ImageDimensions) {
+        let cols = self.child_dims.columns;
+        let rows = self.child_dims.rows;
+        let out: Vec<T> = (0..cols)
+            .flat_map(|r| (0..rows).map(move |c| (r, c)))
+            .map(|(r, c)| self[c * cols + (cols - 1 - r)])
+            .collect();
+        (out, ImageDimensions::new(rows, cols))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+    use super::{CowSliceView, OwnedSliceView};
+    use crate::{ImageDimensions, SliceView, SliceViewError};
+
+    #[cfg(not(feature = "debug-checks"))]
+    #[test]
+    fn owned_copy_indexes_like_source() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let child = ImageDimensions::new(3, 3);
+        let view = SliceView::new(parent, 0, 7, &FRAME_64, child);
+        let owned = view.to_owned();
+        assert_eq!(owned.len(), view.len());
+        for idx in 0..view.len() {
+            assert_eq!(owned[idx], view[idx]);
+        }
+        assert!(owned.iter().eq(view.iter()));
+        assert!(owned.view() == view);
+        assert_eq!(owned.as_slice().len(), 9);
+    }
+
+    #[test]
+    fn from_vec_exact_length() {
+        let dims = ImageDimensions::new(2, 2);
+        let owned = OwnedSliceView::from_vec(dims, vec![1u8, 2, 3, 4]).unwrap();
+        assert_eq!(owned.view()[3], 4);
+        let err = OwnedSliceView::from_vec(dims, vec![1u8, 2, 3, 4, 5]).err();
+        assert_eq!(err, Some(SliceViewError::WrongLength { expected: 4, actual: 5 }));
+        assert!(OwnedSliceView::from_vec(dims, vec![1u8, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn nested_rows() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3, 4));
+        let rows = view.to_2d_vec();
+        assert_eq!(rows.len(), 4);
+        assert!(rows.iter().all(|row| row.len() == 3));
+        assert_eq!(rows[0], [31, 41, 51]);
+        assert_eq!(rows[3][2], 54);
+        assert_eq!(view.transpose().to_2d_vec()[2], [51, 52, 53, 54]);
+    }
+
+    #[test]
+    fn rotate_full_frame_to_vec() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new_passthru(parent, &FRAME_64);
+
+        let (cw, dims) = view.rotate90_cw_to_vec();
+        assert_eq!(dims, parent);
+        assert_eq!(cw[0], 17); // bottom-left becomes top-left
+        assert_eq!(cw[FRAME_64_DIM - 1], 10); // top-left becomes top-right
+
+        let (half, _) = view.rotate180_to_vec();
+        assert_eq!(half[0], 87);
+        assert_eq!(half[63], 10);
+
+        let (ccw, _) = view.rotate270_cw_to_vec();
+        assert_eq!(ccw[0], 80); // top-right becomes top-left
+        assert_eq!(ccw[FRAME_64_DIM * (FRAME_64_DIM - 1)], 10);
+    }
+
+    #[test]
+    fn rotate_non_square_to_vec() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(3, 2));
+        // 31 41 51
+        // 32 42 52
+        let (cw, dims) = view.rotate90_cw_to_vec();
+        assert_eq!(dims, ImageDimensions::new(2, 3));
+        assert_eq!(cw, [32, 31, 42, 41, 52, 51]);
+        let (ccw, dims) = view.rotate270_cw_to_vec();
+        assert_eq!(dims, ImageDimensions::new(2, 3));
+        assert_eq!(ccw, [51, 52, 41, 42, 31, 32]);
+    }
+
+    #[test]
+    fn cow_copies_on_first_write() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(2, 2));
+        let mut cow = CowSliceView::from(view);
+        assert!(cow.view() == view);
+        assert!(!cow.is_owned());
+
+        {
+            let mut pixels = cow.to_mut();
+            assert!(pixels.len() == 4);
+            pixels[0] = 0;
+        }
+        assert!(cow.is_owned());
+        assert_eq!(cow.view()[0], 0);
+        assert_eq!(cow.view()[3], 42);
+        // the source is untouched
+        assert_eq!(view[0], 31);
+
+        cow.to_mut()[1] = 1;
+        let owned = cow.into_owned();
+        assert_eq!(owned.as_slice(), [0, 1, 32, 42]);
+    }
+}
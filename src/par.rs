@@ -0,0 +1,68 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! Parallel iteration over the rows and tiles of views, with `rayon`
+
+use rayon::prelude::*;
+
+use crate::{tile_at, tile_count, ImageDimensions, SliceView, SliceViewMut};
+
+impl<'a, T: Sync> SliceView<'a, T> {
+    /// Parallel version of `enumerate_rows`, without the row indices:
+    /// the rows of the view that are contiguous in the parent slice
+    pub fn par_rows(&self) -> impl ParallelIterator<Item = &'a [T]> {
+        let view = *self;
+        (0..self.row_count()).into_par_iter().filter_map(move |row| view.row_slice(row))
+    }
+}
+
+impl<'a, T: Send + Sync> SliceViewMut<'a, T> {
+    /// Parallel version of `tiles_mut`: non-overlapping tiles of `tile_dims`,
+    /// clipped to fit within the view along its right and bottom edges.
+    /// Panics if the view is wider than the parent stride, since its rows
+    /// (and so its tiles) would then overlap in the parent.
+    pub fn par_tiles(&mut self, tile_dims: ImageDimensions) -> impl ParallelIterator<Item = SliceViewMut<'_, T>> {
+        self.assert_rows_disjoint();
+        let dims = self.child_dims;
+        let view = &*self;
+        (0..tile_count(dims, tile_dims)).into_par_iter().map(move |tile_idx| {
+            let (row, col, tile) = tile_at(dims, tile_dims, tile_idx);
+            // As for `tiles_mut`: tiles lie within the view and are disjoint from one
+            // another, and the view is borrowed mutably for as long as any tile is alive.
+            unsafe { view.alias_window(row, col, tile) }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+    use crate::{ImageDimensions, SliceView, SliceViewMut};
+
+    #[test]
+    fn parallel_row_sum() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, ImageDimensions::new(5, 6));
+        let serial: u32 = view.iter().map(|&px| px as u32).sum();
+        let parallel: u32 = view.par_rows().map(|row| row.iter().map(|&px| px as u32).sum::<u32>()).sum();
+        assert_eq!(parallel, serial);
+        assert_eq!(view.par_rows().count(), 6);
+    }
+
+    #[test]
+    fn parallel_tile_fill() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let mut frame = FRAME_64;
+        let mut view = SliceViewMut::new(parent, 1, 1, &mut frame, ImageDimensions::new(6, 6));
+        view.par_tiles(ImageDimensions::new(4, 4)).for_each(|mut tile| {
+            for px in tile.iter_mut() {
+                *px = 0;
+            }
+        });
+        let zeroed = frame.iter().filter(|&&px| px == 0).count();
+        assert_eq!(zeroed, 36);
+    }
+}
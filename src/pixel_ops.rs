@@ -0,0 +1,570 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! In-place operations on the pixels of a mutable view
+
+use crate::{Scalar, SliceView, SliceViewError, SliceViewMut};
+
+impl<'a, T: Copy> SliceViewMut<'a, T> {
+    /// Paint alternating `square` x `square` blocks of `a` and `b` across the view.
+    /// The block containing the view's local (0,0) is filled with `a`.
+    pub fn fill_checkerboard(&mut self, a: T, b: T, square: usize) {
+        assert!(square > 0, "checkerboard square size must be nonzero");
+        for (row_idx, row) in self.pixel_rows_mut().enumerate() {
+            let row_parity = (row_idx / square) % 2;
+            for (col_idx, px) in row.enumerate() {
+                *px = if (col_idx / square) % 2 == row_parity { a } else { b };
+            }
+        }
+    }
+
+    /// Copy each pixel of `src` into this view where the corresponding pixel of `mask`
+    /// is nonzero, leaving the other pixels of this view untouched (as when blitting
+    /// a sprite with transparency). Panics if the three views differ in dimensions.
+    pub fn copy_from_masked(&mut self, src: &SliceView<T>, mask: &SliceView<u8>) {
+        assert!(
+            self.child_dims == src.child_dims && self.child_dims == mask.child_dims,
+            "views differ in dimensions"
+        );
+        for (row, dst_row) in self.pixel_rows_mut().enumerate() {
+            for (col, px) in dst_row.enumerate() {
+                if *mask.at(row, col) != 0 {
+                    *px = *src.at(row, col);
+                }
+            }
+        }
+    }
+
+    /// Paint `fg` into this view where the pixels of the monochrome `glyph` exceed
+    /// `threshold`, with the glyph's top-left at local `(dest_row, dest_col)`,
+    /// leaving the other pixels untouched. The parts of the glyph that fall
+    /// beyond the right or bottom edge of the view are clipped.
+    pub fn blit_glyph(&mut self, glyph: &SliceView<u8>, dest_row: usize, dest_col: usize, fg: T, threshold: u8) {
+        let glyph_cols = glyph.child_dims.columns;
+        let rows = self.pixel_rows_mut().skip(dest_row).take(glyph.child_dims.rows);
+        for (glyph_row, dst_row) in rows.enumerate() {
+            let dst_pixels = dst_row.skip(dest_col).take(glyph_cols);
+            for (glyph_col, px) in dst_pixels.enumerate() {
+                if *glyph.at(glyph_row, glyph_col) > threshold {
+                    *px = fg;
+                }
+            }
+        }
+    }
+
+    /// Replace each pixel equal to `from` with `to`, returning how many were replaced
+    pub fn replace_value(&mut self, from: T, to: T) -> usize
+    where
+        T: PartialEq,
+    {
+        let mut replaced = 0;
+        for px in self.iter_mut().filter(|px| **px == from) {
+            *px = to;
+            replaced += 1;
+        }
+        replaced
+    }
+
+    /// Reverse each row of the view in place, so local column `col` swaps with
+    /// `columns - 1 - col`. The center column of an odd-width view stays put.
+    pub fn mirror_horizontal_in_place(&mut self) {
+        for mut row in self.pixel_rows_mut() {
+            while let (Some(left), Some(right)) = (row.next(), row.next_back()) {
+                core::mem::swap(left, right);
+            }
+        }
+    }
+
+    /// Rotate the pixels of a square view 90 degrees clockwise, without a scratch buffer,
+    /// by cycling each group of four pixels around the rings of the view.
+    /// Fails without touching the view if it is not square.
+    pub fn rotate90_cw_in_place(&mut self) -> Result<(), SliceViewError> {
+        let n = self.child_dims.columns;
+        if n != self.child_dims.rows {
+            return Err(SliceViewError::NotSquare);
+        }
+        for ring in 0..n / 2 {
+            let last = n - 1 - ring;
+            for i in ring..last {
+                let offset = i - ring;
+                let top = ring * n + i;
+                let right = i * n + last;
+                let bottom = last * n + (last - offset);
+                let left = (last - offset) * n + ring;
+                let saved = self[top];
+                self[top] = self[left];
+                self[left] = self[bottom];
+                self[bottom] = self[right];
+                self[right] = saved;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T: Scalar> SliceViewMut<'a, T> {
+    /// Apply `op` to each pixel of this view and the corresponding pixel of `other`
+    fn zip_assign<F: Fn(T, T) -> T>(&mut self, other: &SliceView<T>, op: F) {
+        assert_eq!(self.child_dims, other.child_dims, "views differ in dimensions");
+        for (row, dst_row) in self.pixel_rows_mut().enumerate() {
+            for (col, px) in dst_row.enumerate() {
+                *px = op(*px, *other.at(row, col));
+            }
+        }
+    }
+
+    /// Add each pixel of `other` to the corresponding pixel of this view,
+    /// saturating at the bounds of `T`. Panics if the views differ in dimensions.
+    pub fn saturating_add_assign(&mut self, other: &SliceView<T>) {
+        self.zip_assign(other, T::saturating_add);
+    }
+
+    /// Subtract each pixel of `other` from the corresponding pixel of this view,
+    /// saturating at the bounds of `T`. Panics if the views differ in dimensions.
+    pub fn saturating_sub_assign(&mut self, other: &SliceView<T>) {
+        self.zip_assign(other, T::saturating_sub);
+    }
+}
+
+/// Copy the pixels of `src` into `dst` one scanline at a time,
+/// honoring the parent strides of both. Panics if the views differ in dimensions.
+/// Rows that are contiguous in both parents are copied with `copy_from_slice`,
+/// others (such as those of the channel views of `per_channel_mut`) pixel by pixel.
+pub fn copy_strided<T: Copy>(src: &SliceView<T>, dst: &mut SliceViewMut<T>) {
+    assert_eq!(src.child_dims, dst.child_dims, "views differ in dimensions");
+    let columns = src.child_dims.columns;
+    if dst.has_contiguous_rows() {
+        for (row, dst_row) in dst.rows_mut().enumerate() {
+            match src.row_slice(row) {
+                Some(src_row) => dst_row.copy_from_slice(src_row),
+                None => {
+                    for (col, px) in dst_row.iter_mut().enumerate() {
+                        *px = src[row * columns + col];
+                    }
+                }
+            }
+        }
+    }
+    else {
+        for (row, dst_row) in dst.pixel_rows_mut().enumerate() {
+            for (col, px) in dst_row.enumerate() {
+                *px = src[row * columns + col];
+            }
+        }
+    }
+}
+
+impl<'a> SliceViewMut<'a, u8> {
+    /// Apply gamma correction, `255 * (px / 255) ^ gamma`, to each pixel of the view.
+    /// The curve is evaluated once per possible pixel value into a lookup table.
+    pub fn gamma(&mut self, gamma: f32) {
+        let mut table = [0u8; 256];
+        for (i, entry) in table.iter_mut().enumerate() {
+            let normalized = i as f32 / 255.0;
+            *entry = libm::roundf(255.0 * libm::powf(normalized, gamma)) as u8;
+        }
+        for row in self.pixel_rows_mut() {
+            for px in row {
+                *px = table[*px as usize];
+            }
+        }
+    }
+
+    /// Blend the solid `color` over each pixel of the view, with an opacity of
+    /// `alpha / 255`: each pixel becomes `(color * alpha + px * (255 - alpha)) / 255`,
+    /// rounded to nearest, so `alpha = 255` paints `color` and `alpha = 0` changes nothing.
+    pub fn fill_blend(&mut self, color: u8, alpha: u8) {
+        let alpha = alpha as u32;
+        let tint = color as u32 * alpha;
+        for row in self.pixel_rows_mut() {
+            for px in row {
+                *px = ((tint + *px as u32 * (255 - alpha) + 127) / 255) as u8;
+            }
+        }
+    }
+
+    /// Posterize the view to `levels` evenly spaced values from 0 to 255.
+    /// The range of inputs is divided into `levels` equal-width buckets,
+    /// each mapped to its representative value `round(bucket * 255 / (levels - 1))`;
+    /// so with 2 levels, pixels below 128 become 0 and the rest 255.
+    /// Panics if `levels` is less than 2.
+    pub fn quantize(&mut self, levels: u8) {
+        assert!(levels >= 2, "quantizing needs at least two levels");
+        let levels = levels as u32;
+        let mut table = [0u8; 256];
+        for (i, entry) in table.iter_mut().enumerate() {
+            let bucket = i as u32 * levels / 256;
+            *entry = ((bucket * 255 + (levels - 1) / 2) / (levels - 1)) as u8;
+        }
+        for row in self.pixel_rows_mut() {
+            for px in row {
+                *px = table[*px as usize];
+            }
+        }
+    }
+
+    /// Linearly remap the pixels of the view so that its darkest pixel becomes 0
+    /// and its brightest 255. A view of a single value is left unchanged.
+    pub fn stretch_contrast(&mut self) {
+        let mut min = u8::MAX;
+        let mut max = u8::MIN;
+        for row in self.pixel_rows_mut() {
+            for &mut px in row {
+                min = min.min(px);
+                max = max.max(px);
+            }
+        }
+        if min >= max {
+            return;
+        }
+        let range = (max - min) as u32;
+        for row in self.pixel_rows_mut() {
+            for px in row {
+                *px = (((*px - min) as u32 * 255 + range / 2) / range) as u8;
+            }
+        }
+    }
+
+    /// Equalize the histogram of the view in place, spreading its pixel values
+    /// across the full range by their cumulative distribution: each value `v` maps to
+    /// `round((cdf(v) - cdf_min) * 255 / (len - cdf_min))`, where `cdf_min` is the count
+    /// of the darkest value present, which so maps to 0. A view of a single value is left unchanged.
+    pub fn equalize_histogram(&mut self) {
+        let mut histogram = [0u32; 256];
+        for row in self.pixel_rows_mut() {
+            for &mut px in row {
+                histogram[px as usize] += 1;
+            }
+        }
+        let total = self.len() as u64;
+        let cdf_min = histogram.iter().copied().find(|&count| count > 0).unwrap_or(0) as u64;
+        if total <= cdf_min {
+            return;
+        }
+        let range = total - cdf_min;
+        let mut table = [0u8; 256];
+        let mut cdf = 0u64;
+        for (entry, &count) in table.iter_mut().zip(histogram.iter()) {
+            cdf += count as u64;
+            *entry = ((cdf.saturating_sub(cdf_min) * 255 + range / 2) / range) as u8;
+        }
+        for row in self.pixel_rows_mut() {
+            for px in row {
+                *px = table[*px as usize];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::copy_strided;
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+    use crate::{ImageDimensions, SliceView, SliceViewError, SliceViewMut};
+
+    #[test]
+    fn checkerboard_unit_squares() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let child = ImageDimensions::new(3, 3);
+        let mut frame = FRAME_64;
+        let mut view = SliceViewMut::new(parent, 2, 3, &mut frame, child);
+        view.fill_checkerboard(0, 255, 1);
+
+        assert_eq!(view[0], 0); // local (0,0)
+        assert_eq!(view[1], 255); // local (0,1)
+        assert_eq!(view[3], 255); // local (1,0)
+        assert_eq!(view[4], 0); // local (1,1)
+        assert_eq!(view[8], 0); // local (2,2)
+        // pixels just outside the view are untouched
+        assert_eq!(frame[2 * FRAME_64_DIM + 2], FRAME_64[2 * FRAME_64_DIM + 2]);
+        assert_eq!(frame[5 * FRAME_64_DIM + 3], FRAME_64[5 * FRAME_64_DIM + 3]);
+    }
+
+    #[test]
+    fn masked_copy_checkerboard() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let dims = ImageDimensions::new(3, 2);
+        let mut mask_pixels = [0u8; 6];
+        SliceViewMut::new_passthru(dims, &mut mask_pixels).fill_checkerboard(1, 0, 1);
+        let mask = SliceView::new_passthru(dims, &mask_pixels);
+        let src = SliceView::new(parent, 1, 2, &FRAME_64, dims);
+
+        let mut frame = [0u8; 16];
+        let mut dst = SliceViewMut::new(ImageDimensions::new(4, 4), 1, 1, &mut frame, dims);
+        dst.copy_from_masked(&src, &mask);
+        assert_eq!(frame, [
+            0, 0, 0, 0,
+            0, 31, 0, 51,
+            0, 0, 42, 0,
+            0, 0, 0, 0,
+        ]);
+    }
+
+    #[test]
+    fn glyph_clipped_at_edges() {
+        // a 3x3 plus sign, with faint pixels that stay off
+        let glyph_pixels = [
+            9u8, 255, 9,
+            255, 255, 255,
+            9, 200, 9,
+        ];
+        let glyph = SliceView::new_passthru(ImageDimensions::new(3, 3), &glyph_pixels);
+        let parent = ImageDimensions::new(5, 4);
+        let dims = ImageDimensions::new(4, 3);
+        let mut frame = [0u8; 20];
+        let mut dst = SliceViewMut::new(parent, 0, 1, &mut frame, dims);
+        // the right column and bottom row of the glyph fall outside the view
+        dst.blit_glyph(&glyph, 1, 2, 1, 127);
+        // glyphs placed wholly outside the view draw nothing
+        dst.blit_glyph(&glyph, 3, 0, 1, 127);
+        dst.blit_glyph(&glyph, 0, 4, 1, 127);
+        assert_eq!(frame, [
+            0, 0, 0, 0, 0,
+            0, 0, 0, 0, 1,
+            0, 0, 0, 1, 1,
+            0, 0, 0, 0, 0,
+        ]);
+    }
+
+    #[test]
+    fn replace_in_window() {
+        let mut frame = [7u8; 16];
+        frame[0] = 3;
+        frame[5] = 3;
+        frame[10] = 3;
+        frame[15] = 3;
+        let mut view = SliceViewMut::new(ImageDimensions::new(4, 4), 1, 1, &mut frame, ImageDimensions::new(2, 2));
+        assert_eq!(view.replace_value(3, 0), 2);
+        assert_eq!(frame, [3, 7, 7, 7, 7, 0, 7, 7, 7, 7, 0, 7, 7, 7, 7, 3]);
+    }
+
+    #[test]
+    fn mirror_window() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let mut frame = FRAME_64;
+        let mut view = SliceViewMut::new(parent, 1, 2, &mut frame, ImageDimensions::new(3, 2));
+        view.mirror_horizontal_in_place();
+        let mirrored: Vec<u8> = view.iter_mut().map(|px| *px).collect();
+        assert_eq!(mirrored, [51, 41, 31, 52, 42, 32]);
+
+        for (idx, (new, old)) in frame.iter().zip(FRAME_64.iter()).enumerate() {
+            let (row, col) = (idx / FRAME_64_DIM, idx % FRAME_64_DIM);
+            if !((1..3).contains(&row) && (2..5).contains(&col)) {
+                assert_eq!(new, old, "at ({}, {})", row, col);
+            }
+        }
+
+        // each channel of interleaved pixels mirrored separately
+        let mut pairs = [1u8, 10, 2, 20, 3, 30, 4, 40, 5, 50, 6, 60];
+        let mut view = SliceViewMut::new_passthru(ImageDimensions::new(6, 2), &mut pairs);
+        view.per_channel_mut(2, |channel| channel.mirror_horizontal_in_place());
+        assert_eq!(pairs, [3, 30, 2, 20, 1, 10, 6, 60, 5, 50, 4, 40]);
+        let mut view = SliceViewMut::new_passthru(ImageDimensions::new(6, 2), &mut pairs);
+        view.per_channel_mut(2, |channel| channel.fill_checkerboard(0, 1, 1));
+        assert_eq!(pairs, [0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn rotate_square_window() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let mut frame = FRAME_64;
+        let mut view = SliceViewMut::new(parent, 1, 2, &mut frame, ImageDimensions::new(3, 3));
+        view.rotate90_cw_in_place().unwrap();
+        // rows of the window were 31 41 51 / 32 42 52 / 33 43 53
+        let rotated: Vec<u8> = view.iter_mut().map(|px| *px).collect();
+        assert_eq!(rotated, [33, 32, 31, 43, 42, 41, 53, 52, 51]);
+
+        for (idx, (new, old)) in frame.iter().zip(FRAME_64.iter()).enumerate() {
+            let (row, col) = (idx / FRAME_64_DIM, idx % FRAME_64_DIM);
+            if !((1..4).contains(&row) && (2..5).contains(&col)) {
+                assert_eq!(new, old, "at ({}, {})", row, col);
+            }
+        }
+
+        let mut view = SliceViewMut::new(parent, 0, 0, &mut frame, ImageDimensions::new(3, 2));
+        assert_eq!(view.rotate90_cw_in_place(), Err(SliceViewError::NotSquare));
+    }
+
+    #[test]
+    fn gamma_darkens_midtones() {
+        let parent = ImageDimensions::new(4, 2);
+        let child = ImageDimensions::new(2, 1);
+        let mut frame = [128u8, 128, 128, 128, 0, 255, 128, 128];
+        let mut view = SliceViewMut::new(parent, 1, 0, &mut frame, child);
+        view.gamma(2.2);
+        // 255 * (128/255)^2.2 = 55.98
+        assert_eq!(frame, [128, 128, 128, 128, 0, 255, 128, 128]);
+        let mut view = SliceViewMut::new(parent, 0, 1, &mut frame, child);
+        view.gamma(2.2);
+        assert_eq!(frame, [128, 56, 56, 128, 0, 255, 128, 128]);
+
+        // round(255 * (v / 255) ^ gamma) for a spread of inputs
+        let inputs = [0u8, 1, 64, 128, 200, 255];
+        let mut levels = inputs;
+        SliceViewMut::new_passthru(ImageDimensions::new(6, 1), &mut levels).gamma(2.2);
+        assert_eq!(levels, [0, 0, 12, 56, 149, 255]);
+        let mut levels = inputs;
+        SliceViewMut::new_passthru(ImageDimensions::new(6, 1), &mut levels).gamma(0.5);
+        assert_eq!(levels, [0, 16, 128, 181, 226, 255]);
+    }
+
+    #[test]
+    fn half_black_overlay() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let mut frame = FRAME_64;
+        let mut view = SliceViewMut::new(parent, 1, 2, &mut frame, ImageDimensions::new(3, 2));
+        view.fill_blend(0, 128);
+        // 31 * 127 / 255 = 15.4
+        assert_eq!(frame[FRAME_64_DIM + 2..FRAME_64_DIM + 5], [15, 20, 25]);
+        for (idx, (new, old)) in frame.iter().zip(FRAME_64.iter()).enumerate() {
+            let (row, col) = (idx / FRAME_64_DIM, idx % FRAME_64_DIM);
+            if (1..3).contains(&row) && (2..5).contains(&col) {
+                assert!((*new as i32 - *old as i32 / 2).abs() <= 1, "at ({}, {})", row, col);
+            }
+            else {
+                assert_eq!(new, old, "at ({}, {})", row, col);
+            }
+        }
+
+        let mut pixels = [0u8, 100, 255];
+        let mut view = SliceViewMut::new_passthru(ImageDimensions::new(3, 1), &mut pixels);
+        view.fill_blend(200, 255);
+        assert_eq!(pixels, [200; 3]);
+        let mut view = SliceViewMut::new_passthru(ImageDimensions::new(3, 1), &mut pixels);
+        view.fill_blend(0, 0);
+        assert_eq!(pixels, [200; 3]);
+    }
+
+    #[test]
+    fn stretch_low_contrast() {
+        let parent = ImageDimensions::new(4, 2);
+        let child = ImageDimensions::new(3, 2);
+        let mut frame = [100u8, 110, 120, 7, 130, 140, 125, 7];
+        let mut view = SliceViewMut::new(parent, 0, 0, &mut frame, child);
+        view.stretch_contrast();
+        assert_eq!(frame, [0, 64, 128, 7, 191, 255, 159, 7]);
+
+        let mut flat = [42u8; 4];
+        let mut view = SliceViewMut::new_passthru(ImageDimensions::new(2, 2), &mut flat);
+        view.stretch_contrast();
+        assert_eq!(flat, [42; 4]);
+
+        // each channel of interleaved pixels stretched separately
+        let mut pairs = [100u8, 7, 110, 9, 120, 8];
+        let mut view = SliceViewMut::new_passthru(ImageDimensions::new(6, 1), &mut pairs);
+        view.per_channel_mut(2, |channel| channel.stretch_contrast());
+        assert_eq!(pairs, [0, 0, 128, 255, 255, 128]);
+    }
+
+    #[test]
+    fn equalize_narrow_band() {
+        let parent = ImageDimensions::new(5, 4);
+        let child = ImageDimensions::new(4, 4);
+        let mut frame = [
+            100u8, 100, 100, 100, 7,
+            100, 100, 100, 100, 7,
+            101, 101, 101, 101, 7,
+            102, 102, 103, 103, 7,
+        ];
+        let mut view = SliceViewMut::new(parent, 0, 0, &mut frame, child);
+        view.equalize_histogram();
+        // the cumulative counts are 8, 12, 14 and all 16 pixels
+        assert_eq!(frame, [
+            0, 0, 0, 0, 7,
+            0, 0, 0, 0, 7,
+            128, 128, 128, 128, 7,
+            191, 191, 255, 255, 7,
+        ]);
+
+        let mut flat = [42u8; 4];
+        let mut view = SliceViewMut::new_passthru(ImageDimensions::new(2, 2), &mut flat);
+        view.equalize_histogram();
+        assert_eq!(flat, [42; 4]);
+    }
+
+    #[test]
+    fn quantize_four_levels() {
+        let parent = ImageDimensions::new(4, 3);
+        let child = ImageDimensions::new(4, 2);
+        let mut frame = [0u8, 63, 64, 127, 128, 191, 192, 255, 9, 99, 199, 250];
+        let mut view = SliceViewMut::new(parent, 0, 0, &mut frame, child);
+        view.quantize(4);
+        assert_eq!(frame, [0, 0, 85, 85, 170, 170, 255, 255, 9, 99, 199, 250]);
+
+        let mut gray = [127u8, 128];
+        SliceViewMut::new_passthru(ImageDimensions::new(2, 1), &mut gray).quantize(2);
+        assert_eq!(gray, [0, 255]);
+    }
+
+    #[test]
+    fn copy_between_strides() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let child = ImageDimensions::new(3, 2);
+        let src = SliceView::new(parent, 1, 2, &FRAME_64, child);
+
+        // 4x3 destination with rows padded to 6 elements
+        const STRIDE: usize = 6;
+        let mut padded = [0xFFu8; STRIDE * 3];
+        let mut dst = SliceViewMut::new_with_stride(ImageDimensions::new(4, 3), STRIDE, 1, 1, &mut padded, child);
+        copy_strided(&src, &mut dst);
+        assert_eq!(padded[STRIDE + 1..STRIDE + 4], [31, 41, 51]);
+        assert_eq!(padded[2 * STRIDE + 1..2 * STRIDE + 4], [32, 42, 52]);
+        assert_eq!(padded.iter().filter(|&&px| px == 0xFF).count(), padded.len() - 6);
+
+        let mut dst = SliceViewMut::new_with_stride(ImageDimensions::new(4, 3), STRIDE, 0, 0, &mut padded, ImageDimensions::new(2, 3));
+        copy_strided(&src.transpose(), &mut dst);
+        assert_eq!(padded[..2], [31, 32]);
+        assert_eq!(padded[2 * STRIDE..2 * STRIDE + 2], [51, 52]);
+
+        // into the second channel of interleaved pairs, whose rows are not contiguous
+        let mut pairs = [0u8; 12];
+        let mut dst = SliceViewMut::new_passthru(ImageDimensions::new(6, 2), &mut pairs);
+        let mut channel = 0;
+        dst.per_channel_mut(2, |dst| {
+            if channel == 1 {
+                copy_strided(&src, dst);
+            }
+            channel += 1;
+        });
+        assert_eq!(pairs, [0, 31, 0, 41, 0, 51, 0, 32, 0, 42, 0, 52]);
+    }
+
+    #[test]
+    fn u8_ops_on_channel_views() {
+        // each op applied per channel matches the op applied to that channel's pixels alone,
+        // which is why these ops step through `pixel_rows_mut` rather than `rows_mut`
+        let interleaved = [10u8, 200, 60, 90, 130, 30, 250, 140];
+        let ops: [fn(&mut SliceViewMut<u8>); 5] = [
+            |view| view.gamma(2.2),
+            |view| view.fill_blend(255, 64),
+            |view| view.quantize(3),
+            |view| view.stretch_contrast(),
+            |view| view.equalize_histogram(),
+        ];
+        for &op in ops.iter() {
+            let mut pixels = interleaved;
+            SliceViewMut::new_passthru(ImageDimensions::new(4, 2), &mut pixels).per_channel_mut(2, op);
+            for channel in 0..2 {
+                let mut alone: Vec<u8> = interleaved.iter().skip(channel).step_by(2).copied().collect();
+                op(&mut SliceViewMut::new_passthru(ImageDimensions::new(2, 2), &mut alone));
+                assert!(pixels.iter().skip(channel).step_by(2).eq(alone.iter()));
+            }
+            assert!(pixels != interleaved);
+        }
+    }
+
+    #[test]
+    fn saturating_accumulate() {
+        let dims = ImageDimensions::new(2, 2);
+        let mut acc = [200u8, 10, 0, 255];
+        let other = [100u8, 20, 5, 1];
+        let mut view = SliceViewMut::new_passthru(dims, &mut acc);
+        view.saturating_add_assign(&SliceView::new_passthru(dims, &other));
+        assert_eq!(acc, [255, 30, 5, 255]);
+
+        let mut view = SliceViewMut::new_passthru(dims, &mut acc);
+        view.saturating_sub_assign(&SliceView::new_passthru(dims, &[0u8, 40, 5, 0]));
+        assert_eq!(acc, [255, 0, 0, 255]);
+    }
+}
@@ -0,0 +1,140 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! A minimal raw byte format for moving views between hosts:
+//! a 16 byte header of the magic `SLVW` then the columns, rows and element size
+//! as little-endian `u32`s, followed by the densely packed pixels in little-endian.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::{ImageDimensions, OwnedSliceView, SliceView, SliceViewError};
+
+const MAGIC: [u8; 4] = *b"SLVW";
+const HEADER_LEN: usize = 16;
+
+/// Element types with a fixed-size little-endian byte representation
+pub trait ToBytes: Copy {
+    /// Number of bytes in the representation
+    const SIZE: usize;
+    /// Append the little-endian bytes of `self` to `out`
+    fn append_le(self, out: &mut Vec<u8>);
+    /// Read a value from exactly `SIZE` little-endian bytes
+    fn from_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_to_bytes {
+    ($($t:ty),*) => {$(
+        impl ToBytes for $t {
+            const SIZE: usize = core::mem::size_of::<$t>();
+
+            fn append_le(self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn from_le(bytes: &[u8]) -> Self {
+                let mut raw = [0u8; core::mem::size_of::<$t>()];
+                raw.copy_from_slice(bytes);
+                <$t>::from_le_bytes(raw)
+            }
+        }
+    )*};
+}
+
+impl_to_bytes!(u8, u16, u32, i8, i16, i32, f32);
+
+impl<'a, T: ToBytes> SliceView<'a, T> {
+    /// Append the header and the viewed pixels to `out`, to be read back by `from_raw_bytes`.
+    /// Fails with `DimensionMismatch`, leaving `out` untouched, if the view is too large
+    /// for its dimensions to be written in the header.
+    pub fn to_raw_bytes(&self, out: &mut Vec<u8>) -> Result<(), SliceViewError> {
+        let mut header = [0u8; HEADER_LEN];
+        header[..4].copy_from_slice(&MAGIC);
+        for (idx, &field) in [self.child_dims.columns, self.child_dims.rows, T::SIZE].iter().enumerate() {
+            let field = u32::try_from(field).map_err(|_| SliceViewError::DimensionMismatch)?;
+            header[4 + 4 * idx..8 + 4 * idx].copy_from_slice(&field.to_le_bytes());
+        }
+        out.reserve(HEADER_LEN + self.len() * T::SIZE);
+        out.extend_from_slice(&header);
+        for &px in self.iter() {
+            px.append_le(out);
+        }
+        Ok(())
+    }
+}
+
+impl<T: ToBytes> OwnedSliceView<T> {
+    /// Read an image written by `to_raw_bytes`. Fails with `BadHeader` if the header
+    /// is missing, is for another element size or describes an image too large to address,
+    /// and with `WrongLength` unless the
+    /// pixel data is exactly the length the header describes.
+    pub fn from_raw_bytes(bytes: &[u8]) -> Result<Self, SliceViewError> {
+        if bytes.len() < HEADER_LEN || bytes[..4] != MAGIC {
+            return Err(SliceViewError::BadHeader);
+        }
+        let field = |idx: usize| {
+            let start = 4 + 4 * idx;
+            u32::from_le_bytes([bytes[start], bytes[start + 1], bytes[start + 2], bytes[start + 3]]) as usize
+        };
+        let dims = ImageDimensions::new(field(0), field(1));
+        if field(2) != T::SIZE {
+            return Err(SliceViewError::BadHeader);
+        }
+        let pixels = &bytes[HEADER_LEN..];
+        let expected = dims.columns.checked_mul(dims.rows)
+            .and_then(|len| len.checked_mul(T::SIZE))
+            .ok_or(SliceViewError::BadHeader)?;
+        if pixels.len() != expected {
+            return Err(SliceViewError::WrongLength { expected, actual: pixels.len() });
+        }
+        let data = pixels.chunks_exact(T::SIZE).map(T::from_le).collect();
+        OwnedSliceView::from_vec(dims, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImageDimensions, OwnedSliceView, SliceView, SliceViewError};
+
+    #[test]
+    fn round_trip_u16() {
+        let frame: Vec<u16> = (0..64).map(|i| i * 1000).collect();
+        let view = SliceView::new(ImageDimensions::new(8, 8), 1, 2, &frame, ImageDimensions::new(3, 2));
+        let mut bytes = Vec::new();
+        view.to_raw_bytes(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 16 + 6 * 2);
+        assert_eq!(bytes[..4], *b"SLVW");
+
+        let owned = OwnedSliceView::<u16>::from_raw_bytes(&bytes).unwrap();
+        assert_eq!(owned.child_dims, view.child_dims);
+        assert!(owned.view() == view);
+
+        assert_eq!(OwnedSliceView::<u8>::from_raw_bytes(&bytes), Err(SliceViewError::BadHeader));
+        bytes[0] = b'X';
+        assert_eq!(OwnedSliceView::<u16>::from_raw_bytes(&bytes), Err(SliceViewError::BadHeader));
+        bytes[0] = b'S';
+        bytes.pop();
+        assert_eq!(
+            OwnedSliceView::<u16>::from_raw_bytes(&bytes),
+            Err(SliceViewError::WrongLength { expected: 12, actual: 11 })
+        );
+    }
+
+    #[test]
+    fn oversized_dims() {
+        let mut bytes = Vec::from(*b"SLVW");
+        for field in [u32::MAX, u32::MAX, 4] {
+            bytes.extend_from_slice(&field.to_le_bytes());
+        }
+        assert_eq!(OwnedSliceView::<u32>::from_raw_bytes(&bytes), Err(SliceViewError::BadHeader));
+
+        if let Some(columns) = (u32::MAX as usize).checked_add(1) {
+            let dims = ImageDimensions::new(columns, 0);
+            let view = SliceView::<u8>::new_passthru(dims, &[]);
+            let mut out = Vec::new();
+            assert_eq!(view.to_raw_bytes(&mut out), Err(SliceViewError::DimensionMismatch));
+            assert!(out.is_empty());
+        }
+    }
+}
@@ -0,0 +1,238 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+use crate::{ImageDimensions, Scalar, SliceView};
+
+/// Method used to calculate output pixels when resampling
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Interpolation {
+    /// Copy the input pixel whose center is closest
+    Nearest,
+    /// Weight the four input pixels surrounding the sample point
+    Bilinear,
+}
+
+/// Map an output coordinate to an input coordinate, treating pixels as
+/// unit squares whose centers lie at `n + 0.5`
+fn map_coord(out_pos: usize, out_len: usize, in_len: usize) -> f32 {
+    (out_pos as f32 + 0.5) * (in_len as f32 / out_len as f32) - 0.5
+}
+
+impl<'a, T: Scalar> SliceView<'a, T> {
+    /// Resample this view into the densely packed `dst` of size `out_dims`.
+    ///
+    /// Output pixel centers are mapped onto the input using the half-pixel-center
+    /// convention: output `(x + 0.5) * in_width / out_width` is the input position,
+    /// so the corners of the input and output images line up rather than the
+    /// centers of their corner pixels. Sample points that fall outside the
+    /// input are clamped to the nearest edge pixel.
+    pub fn resize(&self, out_dims: ImageDimensions, interp: Interpolation, dst: &mut [T]) {
+        let in_cols = self.child_dims.columns;
+        let in_rows = self.child_dims.rows;
+        let out_cols = out_dims.columns;
+        let out_rows = out_dims.rows;
+        assert!(dst.len() >= out_cols * out_rows, "dst too small for out_dims");
+        if out_cols == 0 || out_rows == 0 {
+            return;
+        }
+        assert!(in_cols > 0 && in_rows > 0, "cannot resize an empty view");
+
+        for oy in 0..out_rows {
+            for ox in 0..out_cols {
+                let val = match interp {
+                    Interpolation::Nearest => {
+                        // floor((o + 0.5) * in / out) in integer arithmetic
+                        let sx = ((2 * ox + 1) * in_cols / (2 * out_cols)).min(in_cols - 1);
+                        let sy = ((2 * oy + 1) * in_rows / (2 * out_rows)).min(in_rows - 1);
+                        self[sy * in_cols + sx]
+                    }
+                    Interpolation::Bilinear => {
+                        let sx = map_coord(ox, out_cols, in_cols).max(0.0).min((in_cols - 1) as f32);
+                        let sy = map_coord(oy, out_rows, in_rows).max(0.0).min((in_rows - 1) as f32);
+                        let x0 = sx as usize;
+                        let y0 = sy as usize;
+                        let x1 = (x0 + 1).min(in_cols - 1);
+                        let y1 = (y0 + 1).min(in_rows - 1);
+                        let fx = sx - x0 as f32;
+                        let fy = sy - y0 as f32;
+                        let top = self[y0 * in_cols + x0].to_f32() * (1.0 - fx)
+                            + self[y0 * in_cols + x1].to_f32() * fx;
+                        let bottom = self[y1 * in_cols + x0].to_f32() * (1.0 - fx)
+                            + self[y1 * in_cols + x1].to_f32() * fx;
+                        T::from_f32(top * (1.0 - fy) + bottom * fy)
+                    }
+                };
+                dst[oy * out_cols + ox] = val;
+            }
+        }
+    }
+
+    /// Resample this view into the densely packed `dst` of size `out_dims`,
+    /// weighting input pixels with a user-supplied `kernel`, such as Lanczos or cubic.
+    ///
+    /// Sample points are mapped as for `resize`. Each output pixel is the sum of
+    /// the input pixels within `support` input pixels of its sample point, along
+    /// each axis, weighted by `kernel(dx) * kernel(dy)` of their distances along the axes,
+    /// divided by the sum of the weights. Taps outside the input are clamped to
+    /// the nearest edge pixel. Where the weights sum to zero, the edge-clamped
+    /// input pixel nearest the sample point is used.
+    pub fn resample<F: Fn(f32) -> f32>(&self, out_dims: ImageDimensions, support: f32, kernel: F, dst: &mut [f32]) {
+        let in_cols = self.child_dims.columns;
+        let in_rows = self.child_dims.rows;
+        let out_cols = out_dims.columns;
+        let out_rows = out_dims.rows;
+        assert!(dst.len() >= out_cols * out_rows, "dst too small for out_dims");
+        if out_cols == 0 || out_rows == 0 {
+            return;
+        }
+        assert!(in_cols > 0 && in_rows > 0, "cannot resample an empty view");
+
+        // taps of one axis: first tap position and the number of taps
+        let taps = |center: f32| {
+            let first = libm::ceilf(center - support) as isize;
+            let last = libm::floorf(center + support) as isize;
+            (first, (last - first + 1).max(0) as usize)
+        };
+        let clamp = |pos: isize, len: usize| pos.clamp(0, len as isize - 1) as usize;
+
+        for oy in 0..out_rows {
+            let sy = map_coord(oy, out_rows, in_rows);
+            let (first_y, count_y) = taps(sy);
+            for ox in 0..out_cols {
+                let sx = map_coord(ox, out_cols, in_cols);
+                let (first_x, count_x) = taps(sx);
+                let mut sum = 0.0;
+                let mut weights = 0.0;
+                for y in (first_y..).take(count_y) {
+                    let wy = kernel(y as f32 - sy);
+                    let row = clamp(y, in_rows);
+                    for x in (first_x..).take(count_x) {
+                        let weight = wy * kernel(x as f32 - sx);
+                        sum += weight * self[row * in_cols + clamp(x, in_cols)].to_f32();
+                        weights += weight;
+                    }
+                }
+                dst[oy * out_cols + ox] = if weights != 0.0 {
+                    sum / weights
+                }
+                else {
+                    let nearest_x = clamp(libm::roundf(sx) as isize, in_cols);
+                    let nearest_y = clamp(libm::roundf(sy) as isize, in_rows);
+                    self[nearest_y * in_cols + nearest_x].to_f32()
+                };
+            }
+        }
+    }
+}
+
+impl<'a, T: Ord + Copy> SliceView<'a, T> {
+    /// Reduce each `factor` x `factor` block of the view with `pick`,
+    /// densely packed into `dst`. Partial blocks at the right and bottom edges are dropped.
+    fn pool<F: Fn(T, T) -> T>(&self, factor: usize, dst: &mut [T], pick: F) {
+        assert!(factor > 0, "pooling factor must be nonzero");
+        let out_cols = self.child_dims.columns / factor;
+        let out_rows = self.child_dims.rows / factor;
+        assert!(dst.len() >= out_cols * out_rows, "dst too small for pooled view");
+        for oy in 0..out_rows {
+            for ox in 0..out_cols {
+                let block = self.sub_view(oy * factor, ox * factor, ImageDimensions::new(factor, factor));
+                let mut pixels = block.iter().copied();
+                let first = pixels.next().unwrap();
+                dst[oy * out_cols + ox] = pixels.fold(first, &pick);
+            }
+        }
+    }
+
+    /// Max-pool the view: write the maximum of each `factor` x `factor` block into `dst`,
+    /// which is densely packed with dimensions `child_dims / factor` (rounded down)
+    pub fn pool_max(&self, factor: usize, dst: &mut [T]) {
+        self.pool(factor, dst, core::cmp::max);
+    }
+
+    /// Min-pool the view, like `pool_max`
+    pub fn pool_min(&self, factor: usize, dst: &mut [T]) {
+        self.pool(factor, dst, core::cmp::min);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+
+    #[test]
+    fn resize_nearest_up() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let child = ImageDimensions::new(4, 4);
+        let view = SliceView::new(parent, 2, 2, &FRAME_64, child);
+
+        let mut out = [0u8; 64];
+        view.resize(ImageDimensions::new(8, 8), Interpolation::Nearest, &mut out);
+        for oy in 0..8 {
+            for ox in 0..8 {
+                assert_eq!(out[oy * 8 + ox], view[(oy / 2) * 4 + ox / 2]);
+            }
+        }
+        assert_eq!(out[0], 32);
+        assert_eq!(out[63], 65);
+    }
+
+    #[test]
+    fn resize_bilinear_down() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let child = ImageDimensions::new(4, 4);
+        let view = SliceView::new(parent, 2, 2, &FRAME_64, child);
+
+        // each output pixel center lands between four input pixel centers
+        let mut out = [0u8; 4];
+        view.resize(ImageDimensions::new(2, 2), Interpolation::Bilinear, &mut out);
+        assert_eq!(out, [38, 58, 40, 60]); // means of 32,42,33,43 etc, rounded
+    }
+
+    #[test]
+    fn resample_box_averages() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 2, 2, &FRAME_64, ImageDimensions::new(4, 4));
+        let mut out = [0f32; 4];
+        let unit_box = |d: f32| if d.abs() <= 0.5 { 1.0 } else { 0.0 };
+        view.resample(ImageDimensions::new(2, 2), 1.0, unit_box, &mut out);
+        // means of 32,42,33,43 etc
+        assert_eq!(out, [37.5, 57.5, 39.5, 59.5]);
+    }
+
+    #[test]
+    fn resample_triangle_is_bilinear() {
+        let frame: Vec<f32> = FRAME_64.iter().map(|&px| px as f32).collect();
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 2, &frame, ImageDimensions::new(4, 3));
+        let out_dims = ImageDimensions::new(7, 5);
+        let mut bilinear = [0f32; 35];
+        view.resize(out_dims, Interpolation::Bilinear, &mut bilinear);
+        let mut triangle = [0f32; 35];
+        view.resample(out_dims, 1.0, |d| (1.0 - d.abs()).max(0.0), &mut triangle);
+        for (a, b) in bilinear.iter().zip(triangle.iter()) {
+            assert!((a - b).abs() < 1e-4, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn pool_frame() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new_passthru(parent, &FRAME_64);
+        let mut out = [0u8; 16];
+        view.pool_max(2, &mut out);
+        assert_eq!(out[0], 21);
+        assert_eq!(out[5], 43);
+        assert_eq!(out[15], 87);
+        view.pool_min(2, &mut out);
+        assert_eq!(out[0], 10);
+        assert_eq!(out[15], 76);
+
+        // the odd bottom row and right column are dropped
+        let view = SliceView::new(parent, 0, 0, &FRAME_64, ImageDimensions::new(5, 3));
+        let mut out = [0u8; 2];
+        view.pool_max(2, &mut out);
+        assert_eq!(out, [21, 41]);
+    }
+}
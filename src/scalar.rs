@@ -0,0 +1,57 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+
+/// Numeric pixel types that can be converted to and from `f32`
+pub trait Scalar: Copy + PartialEq {
+    fn to_f32(self) -> f32;
+    /// Convert from `f32`, rounding to nearest and saturating at the bounds of `Self`
+    fn from_f32(val: f32) -> Self;
+    /// Magnitude of the difference between two values. For floating point this is
+    /// rounded up, so that any difference is nonzero, and is `u32::MAX` for NaN.
+    fn abs_diff(self, other: Self) -> u32;
+    /// Addition that clamps at the bounds of `Self` rather than wrapping
+    fn saturating_add(self, other: Self) -> Self;
+    /// Subtraction that clamps at the bounds of `Self` rather than wrapping
+    fn saturating_sub(self, other: Self) -> Self;
+}
+
+/// Round half away from zero, without relying on `std`
+fn round_f32(val: f32) -> f32 {
+    if val < 0.0 { val - 0.5 } else { val + 0.5 }
+}
+
+macro_rules! impl_int_scalar {
+    ($($t:ty),*) => {
+        $(
+        impl Scalar for $t {
+            fn to_f32(self) -> f32 { self as f32 }
+            // `as` casts from float truncate toward zero and saturate
+            fn from_f32(val: f32) -> Self { round_f32(val) as $t }
+            fn abs_diff(self, other: Self) -> u32 {
+                (self as i64 - other as i64).unsigned_abs() as u32
+            }
+            fn saturating_add(self, other: Self) -> Self { <$t>::saturating_add(self, other) }
+            fn saturating_sub(self, other: Self) -> Self { <$t>::saturating_sub(self, other) }
+        }
+        )*
+    };
+}
+
+impl_int_scalar!(u8, u16, u32, i8, i16, i32);
+
+impl Scalar for f32 {
+    fn to_f32(self) -> f32 { self }
+    fn from_f32(val: f32) -> Self { val }
+    fn abs_diff(self, other: Self) -> u32 {
+        let diff = self - other;
+        if diff.is_nan() {
+            return u32::MAX;
+        }
+        // float to int `as` casts saturate
+        libm::ceilf(if diff < 0.0 { -diff } else { diff }) as u32
+    }
+    fn saturating_add(self, other: Self) -> Self { self + other }
+    fn saturating_sub(self, other: Self) -> Self { self - other }
+}
@@ -0,0 +1,196 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+use crate::{ImageDimensions, Scalar, SliceView};
+
+#[cfg(feature = "memchr")]
+fn find_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+    memchr::memchr(needle, haystack)
+}
+
+#[cfg(not(feature = "memchr"))]
+fn find_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|&px| px == needle)
+}
+
+impl<'a> SliceView<'a, u8> {
+    /// Find the first pixel in row-major order equal to `needle`,
+    /// returning its local `(row, col)`.
+    /// Each row is searched as a contiguous slice, using `memchr` when that feature is enabled.
+    pub fn find_value(&self, needle: u8) -> Option<(usize, usize)> {
+        let columns = self.child_dims.columns;
+        for row in 0..self.child_dims.rows {
+            let found = match self.row_slice(row) {
+                Some(line) => find_byte(needle, line),
+                None => (0..columns).position(|col| self[row * columns + col] == needle),
+            };
+            if let Some(col) = found {
+                return Some((row, col));
+            }
+        }
+        None
+    }
+}
+
+impl<'a, T: Scalar> SliceView<'a, T> {
+    /// Slide `template` over every position where it fits entirely within this view,
+    /// returning the local `(row, col)` of the best match (lowest SAD) along with its SAD.
+    /// Ties resolve to the first position in row-major order.
+    /// Returns `None` if the template is empty or larger than this view.
+    pub fn match_template(&self, template: &SliceView<T>) -> Option<(usize, usize, u64)> {
+        let dims = template.child_dims;
+        if template.is_empty()
+            || dims.columns > self.child_dims.columns
+            || dims.rows > self.child_dims.rows
+        {
+            return None;
+        }
+        let mut best: Option<(usize, usize, u64)> = None;
+        for row in 0..=(self.child_dims.rows - dims.rows) {
+            for col in 0..=(self.child_dims.columns - dims.columns) {
+                let score = self.sub_view(row, col, dims).sad(template);
+                if best.map_or(true, |(_, _, best_score)| score < best_score) {
+                    best = Some((row, col, score));
+                }
+            }
+        }
+        best
+    }
+
+    /// Estimate the integer offset between two views of the same scene:
+    /// search the shifts `(dr, dc)` in `[-max_shift, max_shift]` along each axis
+    /// for the one where `other[(r + dr, c + dc)]` best matches `self[(r, c)]`,
+    /// returning it along with the SAD over the pixels the views then have in common.
+    ///
+    /// Larger shifts leave fewer pixels in common, so shifts are ranked by their
+    /// SAD per common pixel rather than the raw SAD. So that a sliver of overlap
+    /// cannot match by chance, only shifts leaving at least half as many pixels in
+    /// common as the zero shift are considered. Ties resolve to the smallest shift,
+    /// by `|dr| + |dc|`, then to the first in row-major order. Panics if either view is empty.
+    pub fn best_shift_match(&self, other: &SliceView<T>, max_shift: usize) -> (isize, isize, u64) {
+        assert!(!self.is_empty() && !other.is_empty(), "cannot match empty views");
+        // the local range of self along an axis that still lies within other once shifted
+        let overlap = |shift: isize, len: usize, other_len: usize| {
+            let start = (-shift).max(0);
+            let end = (len as isize).min(other_len as isize - shift);
+            (start, end - start)
+        };
+        let max_shift = max_shift as isize;
+        // no shift has more pixels in common than the zero shift
+        let most_common = self.child_dims.rows.min(other.child_dims.rows)
+            * self.child_dims.columns.min(other.child_dims.columns);
+        let least_common = (most_common as u64).div_ceil(2);
+        // SAD and the number of pixels it covers
+        let mut best: Option<(isize, isize, u64, u64)> = None;
+        for dr in -max_shift..=max_shift {
+            let (row, rows) = overlap(dr, self.child_dims.rows, other.child_dims.rows);
+            if rows <= 0 {
+                continue;
+            }
+            for dc in -max_shift..=max_shift {
+                let (col, columns) = overlap(dc, self.child_dims.columns, other.child_dims.columns);
+                if columns <= 0 {
+                    continue;
+                }
+                let dims = ImageDimensions::new(columns as usize, rows as usize);
+                let ours = self.sub_view(row as usize, col as usize, dims);
+                let theirs = other.sub_view((row + dr) as usize, (col + dc) as usize, dims);
+                let score = ours.sad(&theirs);
+                let count = ours.len() as u64;
+                if count < least_common {
+                    continue;
+                }
+                let better = best.map_or(true, |(best_dr, best_dc, best_score, best_count)| {
+                    let ours = (score as u128) * (best_count as u128);
+                    let theirs = (best_score as u128) * (count as u128);
+                    ours < theirs || (ours == theirs && dr.abs() + dc.abs() < best_dr.abs() + best_dc.abs())
+                });
+                if better {
+                    best = Some((dr, dc, score, count));
+                }
+            }
+        }
+        // the zero shift always overlaps, so some shift was scored
+        let (dr, dc, score, _) = best.unwrap();
+        (dr, dc, score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+    use crate::{ImageDimensions, SliceView};
+
+    #[test]
+    fn find_marker() {
+        const MARKER: u8 = 0xAA;
+        let mut frame = FRAME_64;
+        frame[4 * FRAME_64_DIM + 5] = MARKER;
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 2, 3, &frame, ImageDimensions::new(4, 4));
+        assert_eq!(view.find_value(MARKER), Some((2, 2)));
+        assert_eq!(view.transpose().find_value(MARKER), Some((2, 2)));
+        assert_eq!(view.find_value(43), Some((1, 0)));
+        // present in the parent but outside the view
+        assert_eq!(view.find_value(10), None);
+    }
+
+    #[test]
+    fn locate_template() {
+        let pattern = [200u8, 0, 0, 200];
+        let mut frame = FRAME_64;
+        frame[5 * FRAME_64_DIM + 3] = 200;
+        frame[5 * FRAME_64_DIM + 4] = 0;
+        frame[6 * FRAME_64_DIM + 3] = 0;
+        frame[6 * FRAME_64_DIM + 4] = 200;
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new(parent, 1, 1, &frame, ImageDimensions::new(6, 6));
+        let template = SliceView::new_passthru(ImageDimensions::new(2, 2), &pattern);
+        assert_eq!(view.match_template(&template), Some((4, 2, 0)));
+
+        let too_big = SliceView::new_passthru(parent, &FRAME_64);
+        assert_eq!(view.match_template(&too_big), None);
+    }
+
+    #[test]
+    fn recover_shift() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let dims = ImageDimensions::new(6, 6);
+        let view = SliceView::new(parent, 1, 0, &FRAME_64, dims);
+        assert_eq!(view.best_shift_match(&view, 2), (0, 0, 0));
+
+        // the scene moved down one row and left two columns
+        let other = SliceView::new(parent, 0, 2, &FRAME_64, dims);
+        assert_eq!(other[(1, 0)], view[(0, 2)]);
+        assert_eq!(view.best_shift_match(&other, 2), (1, -2, 0));
+        assert_eq!(other.best_shift_match(&view, 2), (-1, 2, 0));
+        // out of range of the search, only a worse match is found
+        let (_, _, score) = view.best_shift_match(&other, 1);
+        assert!(score > 0);
+
+        // a larger other view, where the whole of self overlaps
+        let whole = SliceView::new_passthru(parent, &FRAME_64);
+        assert_eq!(view.best_shift_match(&whole, 3), (1, 0, 0));
+    }
+
+    #[test]
+    fn flat_scene_stays_put() {
+        let flat = [7u8; 16];
+        let view = SliceView::new_passthru(ImageDimensions::new(4, 4), &flat);
+        assert_eq!(view.best_shift_match(&view, 2), (0, 0, 0));
+    }
+
+    #[test]
+    fn sliver_overlap_loses() {
+        let dims = ImageDimensions::new(4, 4);
+        let scene: [u8; 16] = core::array::from_fn(|idx| ((idx * 97 + 13) % 251) as u8);
+        let view = SliceView::new_passthru(dims, &scene);
+        // the same scene with a little noise, but with its top-left pixel
+        // matching the bottom-right of view exactly
+        let mut noisy = scene.map(|px| px + 1);
+        noisy[0] = scene[15];
+        let other = SliceView::new_passthru(dims, &noisy);
+        assert_eq!(view.best_shift_match(&other, 3), (0, 0, 15 + (scene[15] - scene[0]) as u64));
+    }
+}
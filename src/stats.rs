@@ -0,0 +1,309 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! Reductions over the pixels of a view
+
+use crate::{ImageDimensions, Scalar, SliceView};
+
+impl<'a, T> SliceView<'a, T> {
+    /// Fold over the pixels of the view in row-major order.
+    /// Equivalent to `self.iter().fold(init, f)`, so an empty view yields `init`.
+    pub fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.iter().fold(init, f)
+    }
+
+    /// Reduce each tile of `tile_dims` (as yielded by `tiles`) with `f`, writing the
+    /// results densely packed into `dst`, one per tile in row-major tile order:
+    /// for example a blocky downscale with `|tile| tile.mean()`.
+    pub fn reduce_tiles<O, F: FnMut(&SliceView<T>) -> O>(&self, tile_dims: ImageDimensions, mut f: F, dst: &mut [O]) {
+        for (tile_idx, tile) in self.tiles(tile_dims).enumerate() {
+            assert!(tile_idx < dst.len(), "dst too small for tiles");
+            dst[tile_idx] = f(&tile);
+        }
+    }
+}
+
+impl<'a, T: PartialEq + Copy> SliceView<'a, T> {
+    /// Call `emit(row, value, run_length)` for each run of equal pixels
+    /// along each row of the view, left to right, as the front end of an RLE encoder.
+    /// Runs never continue from one row into the next.
+    pub fn row_runs<F: FnMut(usize, T, usize)>(&self, mut emit: F) {
+        for (row_idx, row) in self.rows().enumerate() {
+            let mut pixels = row.iter().copied();
+            let Some(mut value) = pixels.next() else { continue };
+            let mut run = 1;
+            for px in pixels {
+                if px == value {
+                    run += 1;
+                }
+                else {
+                    emit(row_idx, value, run);
+                    value = px;
+                    run = 1;
+                }
+            }
+            emit(row_idx, value, run);
+        }
+    }
+}
+
+/// Summary of the differences between two same-size views
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RegionDiff {
+    /// Number of pixels that differ
+    pub mismatched: usize,
+    /// Largest magnitude of difference between corresponding pixels
+    pub max_abs_diff: u32,
+    /// Local `(row, col)` of the first differing pixel in row-major order
+    pub first_mismatch: Option<(usize, usize)>,
+}
+
+impl<'a, T: Scalar> SliceView<'a, T> {
+    /// Compare each pixel with the corresponding pixel of `other`.
+    /// Panics if the views have different dimensions.
+    pub fn compare(&self, other: &SliceView<T>) -> RegionDiff {
+        assert_eq!(self.child_dims, other.child_dims, "views differ in dimensions");
+        let columns = self.child_dims.columns;
+        let mut diff = RegionDiff::default();
+        for (idx, (&a, &b)) in self.iter().zip(other.iter()).enumerate() {
+            if a != b {
+                diff.mismatched += 1;
+                diff.max_abs_diff = diff.max_abs_diff.max(a.abs_diff(b));
+                if diff.first_mismatch.is_none() {
+                    diff.first_mismatch = Some((idx / columns, idx % columns));
+                }
+            }
+        }
+        diff
+    }
+
+    /// Sum of absolute differences between corresponding pixels of two views,
+    /// the usual block-matching metric.
+    /// Panics if the views have different dimensions.
+    pub fn sad(&self, other: &SliceView<T>) -> u64 {
+        assert_eq!(self.child_dims, other.child_dims, "views differ in dimensions");
+        self.iter()
+            .zip(other.iter())
+            .map(|(&a, &b)| a.abs_diff(b) as u64)
+            .sum()
+    }
+
+    /// Write a change mask densely packed into `dst`: 255 where corresponding pixels
+    /// differ by more than `threshold`, and 0 elsewhere.
+    /// Panics if the views have different dimensions.
+    pub fn diff_mask_into(&self, other: &SliceView<T>, threshold: u32, dst: &mut [u8]) {
+        assert_eq!(self.child_dims, other.child_dims, "views differ in dimensions");
+        assert!(dst.len() >= self.len(), "dst too small for view");
+        for (out, (&a, &b)) in dst.iter_mut().zip(self.iter().zip(other.iter())) {
+            *out = if a.abs_diff(b) > threshold { 255 } else { 0 };
+        }
+    }
+}
+
+impl<'a, T: Copy + Into<u32>> SliceView<'a, T> {
+    /// Add each pixel of the view into the corresponding entry of `acc`,
+    /// which holds a running sum per pixel, densely packed in row-major order
+    /// (for example across frames, to average them with `finalize_mean`).
+    /// Sums saturate at `u32::MAX`. Panics unless `acc` has one entry per pixel.
+    pub fn accumulate_into(&self, acc: &mut [u32]) {
+        assert_eq!(acc.len(), self.len(), "accumulator size differs from view");
+        for (sum, &px) in acc.iter_mut().zip(self.iter()) {
+            *sum = sum.saturating_add(px.into());
+        }
+    }
+}
+
+/// Divide the per-pixel sums of `count` frames in `acc` (see `accumulate_into`)
+/// into `dst`, rounding to the nearest value and saturating at 255.
+/// Panics if `count` is zero or if `acc` and `dst` differ in length.
+pub fn finalize_mean(acc: &[u32], count: u32, dst: &mut [u8]) {
+    assert!(count > 0, "mean of zero frames");
+    assert_eq!(acc.len(), dst.len(), "accumulator size differs from destination");
+    for (out, &sum) in dst.iter_mut().zip(acc.iter()) {
+        let mean = (sum as u64 + count as u64 / 2) / count as u64;
+        *out = mean.min(u8::MAX as u64) as u8;
+    }
+}
+
+impl<'a, T: Copy + Into<f32>> SliceView<'a, T> {
+    /// Single-pass (Welford) mean and sum of squared deviations from the mean
+    fn welford(&self) -> (f32, f32) {
+        let mut count = 0u32;
+        let mut mean = 0.0f32;
+        let mut m2 = 0.0f32;
+        for &px in self.iter() {
+            let val: f32 = px.into();
+            count += 1;
+            let delta = val - mean;
+            mean += delta / count as f32;
+            m2 += delta * (val - mean);
+        }
+        (mean, m2)
+    }
+
+    /// Mean pixel value, or 0 for an empty view
+    pub fn mean(&self) -> f32 {
+        self.welford().0
+    }
+
+    /// Population variance of the pixel values, or 0 for an empty view.
+    /// Computed in a single pass that avoids the cancellation error of
+    /// subtracting the squared mean from the mean of squares.
+    pub fn variance(&self) -> f32 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        self.welford().1 / self.len() as f32
+    }
+
+    /// Population standard deviation of the pixel values
+    pub fn std_dev(&self) -> f32 {
+        libm::sqrtf(self.variance())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+    use super::{finalize_mean, RegionDiff};
+    use crate::{ImageDimensions, SliceView};
+
+    #[test]
+    fn weighted_sum_fold() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let child = ImageDimensions::new(2, 2);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, child);
+        // pixels 31, 41, 32, 42 weighted 1, 2, 3, 4
+        let (sum, _) = view.fold((0u32, 1u32), |(sum, weight), &px| {
+            (sum + weight * px as u32, weight + 1)
+        });
+        assert_eq!(sum, 31 + 2 * 41 + 3 * 32 + 4 * 42);
+    }
+
+    #[test]
+    fn tile_sums() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let view = SliceView::new_passthru(parent, &FRAME_64);
+        let mut sums = [0u32; 16];
+        view.reduce_tiles(ImageDimensions::new(2, 2), |tile| tile.fold(0, |sum, &px| sum + px as u32), &mut sums);
+        // tile (r, c) holds a = 10*(2c+1) + 2r, b = a + 10, and each of those plus one
+        for (idx, &sum) in sums.iter().enumerate() {
+            let (r, c) = ((idx / 4) as u32, (idx % 4) as u32);
+            assert_eq!(sum, 2 * (10 * (2 * c + 1) + 2 * r) + 2 * (10 * (2 * c + 2) + 2 * r) + 2);
+        }
+        assert_eq!(sums[0], 10 + 20 + 11 + 21);
+    }
+
+    #[test]
+    fn runs_per_row() {
+        let frame = [9u8, 5, 5, 5, 2, 2, 9, 7, 7, 7, 7, 7];
+        let parent = ImageDimensions::new(6, 2);
+        let view = SliceView::new(parent, 0, 1, &frame, ImageDimensions::new(5, 2));
+        let mut runs = Vec::new();
+        view.row_runs(|row, value, len| runs.push((row, value, len)));
+        assert_eq!(runs, [(0, 5, 3), (0, 2, 2), (1, 7, 5)]);
+    }
+
+    #[test]
+    fn change_mask() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let child = ImageDimensions::new(3, 2);
+        let mut frame = FRAME_64;
+        frame[FRAME_64_DIM + 2] += 10;
+        frame[2 * FRAME_64_DIM + 3] -= 3;
+        frame[2 * FRAME_64_DIM + 4] += 4;
+        let before = SliceView::new(parent, 1, 2, &FRAME_64, child);
+        let after = SliceView::new(parent, 1, 2, &frame, child);
+        let mut mask = [0xAAu8; 6];
+        before.diff_mask_into(&after, 3, &mut mask);
+        assert_eq!(mask, [255, 0, 0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn mean_over_frames() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let child = ImageDimensions::new(2, 2);
+        let mut acc = [0u32; 4];
+        for offset in [0u8, 1, 5] {
+            let mut frame = FRAME_64;
+            frame.iter_mut().for_each(|px| *px += offset);
+            SliceView::new(parent, 1, 2, &frame, child).accumulate_into(&mut acc);
+        }
+        // 31, 41, 32, 42 plus a mean offset of 2
+        assert_eq!(acc, [99, 129, 102, 132]);
+        let mut mean = [0u8; 4];
+        finalize_mean(&acc, 3, &mut mean);
+        assert_eq!(mean, [33, 43, 34, 44]);
+    }
+
+    #[test]
+    fn variance_std_dev() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let child = ImageDimensions::new(2, 2);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, child);
+        // 31, 41, 32, 42: deviations from 36.5 are -5.5, 4.5, -4.5, 5.5
+        assert!((view.mean() - 36.5).abs() < 1e-5);
+        assert!((view.variance() - 25.25).abs() < 1e-4);
+        assert!((view.std_dev() - 5.024938).abs() < 1e-4);
+
+        // large offset values where the naive formula loses precision
+        let big = [100_001.0f32, 100_002.0, 100_003.0, 100_006.0];
+        let view = SliceView::new_passthru(child, &big);
+        assert!((view.variance() - 3.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn compare_two_mismatches() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let child = ImageDimensions::new(3, 3);
+        let mut frame = FRAME_64;
+        frame[2 * FRAME_64_DIM + 3] = 0; // local (1,1) was 42
+        frame[3 * FRAME_64_DIM + 4] = 50; // local (2,2) was 53
+        let reference = SliceView::new(parent, 1, 2, &FRAME_64, child);
+        let computed = SliceView::new(parent, 1, 2, &frame, child);
+
+        assert_eq!(reference.compare(&reference), RegionDiff::default());
+        let diff = reference.compare(&computed);
+        assert_eq!(diff.mismatched, 2);
+        assert_eq!(diff.max_abs_diff, 42);
+        assert_eq!(diff.first_mismatch, Some((1, 1)));
+    }
+
+    #[test]
+    fn compare_fractional_floats() {
+        let dims = ImageDimensions::new(2, 1);
+        let zeros = [0.0f32, 0.0];
+        let close = [0.5f32, 0.9];
+        let zeros = SliceView::new_passthru(dims, &zeros);
+        let close = SliceView::new_passthru(dims, &close);
+        let diff = zeros.compare(&close);
+        assert_eq!(diff.mismatched, 2);
+        // fractional differences round up to 1
+        assert_eq!(diff.max_abs_diff, 1);
+        assert_eq!(diff.first_mismatch, Some((0, 0)));
+        assert_eq!(zeros.sad(&close), 2);
+        let mut mask = [0u8; 2];
+        zeros.diff_mask_into(&close, 0, &mut mask);
+        assert_eq!(mask, [255, 255]);
+
+        let nan = [f32::NAN, 2.25];
+        let nan = SliceView::new_passthru(dims, &nan);
+        let diff = nan.compare(&nan);
+        assert_eq!(diff.mismatched, 1);
+        assert_eq!(diff.max_abs_diff, u32::MAX);
+        assert_eq!(close.compare(&close), RegionDiff::default());
+    }
+
+    #[test]
+    fn sad_blocks() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let child = ImageDimensions::new(3, 3);
+        let block = SliceView::new(parent, 2, 2, &FRAME_64, child);
+        assert_eq!(block.sad(&block), 0);
+        // one column right: each pixel is 10 greater
+        let shifted = SliceView::new(parent, 2, 3, &FRAME_64, child);
+        assert_eq!(block.sad(&shifted), 90);
+        assert_eq!(shifted.sad(&block), 90);
+    }
+}
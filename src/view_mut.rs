@@ -0,0 +1,428 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+use core::marker::PhantomData;
+use core::ops::{Index, IndexMut};
+
+use crate::{tile_grid, translate, ImageDimensions};
+
+/// A mutable view of a portion of an image stored in a slice.
+///
+/// A view only ever accesses the pixels inside its own window of the parent,
+/// which allows several views of disjoint windows of one parent
+/// (such as those from `tiles_mut`) to be used at the same time.
+pub struct SliceViewMut<'a, T> {
+    pub parent_dims: ImageDimensions,
+    pub child_dims: ImageDimensions,
+    parent_start_col: usize,
+    parent_start_row: usize,
+    /// Number of elements between the starts of adjacent parent rows
+    parent_stride: usize,
+    /// Number of elements between adjacent pixels of a parent row
+    pixel_stride: usize,
+    /// Start of the parent slice
+    ptr: *mut T,
+    /// Length of the parent slice
+    parent_len: usize,
+    _parent: PhantomData<&'a mut [T]>,
+}
+
+// Same as for `&mut [T]`: only the pixels of the window are ever accessed
+unsafe impl<'a, T: Send> Send for SliceViewMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for SliceViewMut<'a, T> {}
+
+impl<'a, T> SliceViewMut<'a, T> {
+    pub fn new(parent_dims: ImageDimensions, parent_start_row: usize, parent_start_col: usize, slice: &'a mut [T], child_dims: ImageDimensions) -> Self {
+        Self::new_with_stride(parent_dims, parent_dims.columns, parent_start_row, parent_start_col, slice, child_dims)
+    }
+
+    /// Like `new`, but for parent images whose rows are padded:
+    /// `parent_stride` is the number of elements between the starts of adjacent rows.
+    pub fn new_with_stride(parent_dims: ImageDimensions, parent_stride: usize, parent_start_row: usize, parent_start_col: usize, slice: &'a mut [T], child_dims: ImageDimensions) -> Self {
+        #[cfg(feature = "debug-checks")]
+        debug_assert!(
+            crate::check_window(parent_dims, parent_stride, parent_start_row, parent_start_col, child_dims, slice.len()).is_ok(),
+            "view window does not fit within parent"
+        );
+        Self {
+            parent_dims,
+            child_dims,
+            parent_start_col,
+            parent_start_row,
+            parent_stride,
+            pixel_stride: 1,
+            ptr: slice.as_mut_ptr(),
+            parent_len: slice.len(),
+            _parent: PhantomData,
+        }
+    }
+
+    /// Simply wrap an existing mutable slice
+    pub fn new_passthru(parent_dims: ImageDimensions, slice: &'a mut [T]) -> Self {
+        Self::new(parent_dims, 0, 0, slice, parent_dims)
+    }
+
+    /// Number of pixels in the view
+    pub fn len(&self) -> usize {
+        self.child_dims.columns * self.child_dims.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Translate a local index within this view into an index in the parent slice.
+    /// Panics if the view is empty, having no pixels to translate.
+    pub fn local_index_to_parent_index(&self, idx: usize) -> usize {
+        assert!(!self.is_empty(), "index {} into an empty view", idx);
+        if self.pixel_stride == 1 {
+            return translate(idx, self.child_dims.columns, self.parent_start_row, self.parent_start_col, self.parent_stride);
+        }
+        let row = self.parent_start_row + idx / self.child_dims.columns;
+        let col = self.parent_start_col + idx % self.child_dims.columns;
+        row * self.parent_stride + col * self.pixel_stride
+    }
+
+    /// Pointer to the pixel at local index `idx`, which must be within the view
+    fn pixel_ptr(&self, idx: usize) -> *mut T {
+        assert!(idx < self.len(), "index {} outside view of {} pixels", idx, self.len());
+        let parent_idx = self.local_index_to_parent_index(idx);
+        assert!(parent_idx < self.parent_len, "view extends beyond parent slice");
+        // in bounds of the parent slice, checked above
+        unsafe { self.ptr.add(parent_idx) }
+    }
+
+    /// Whether each row of the view is contiguous in the parent, as `rows_mut` requires
+    pub(crate) fn has_contiguous_rows(&self) -> bool {
+        self.pixel_stride == 1
+    }
+
+    /// Panics if the rows of the view span more than the parent stride,
+    /// since adjacent rows would then overlap in the parent
+    pub(crate) fn assert_rows_disjoint(&self) {
+        let columns = self.child_dims.columns;
+        assert!(
+            columns == 0 || (columns - 1) * self.pixel_stride < self.parent_stride,
+            "view rows overlap in the parent"
+        );
+    }
+
+    /// A view of a window of this view, sharing its parent.
+    ///
+    /// Safety: the window must lie within this view, and the caller must ensure
+    /// that coexisting views returned from this have disjoint windows.
+    pub(crate) unsafe fn alias_window(&self, row: usize, col: usize, dims: ImageDimensions) -> Self {
+        Self {
+            child_dims: dims,
+            parent_start_row: self.parent_start_row + row,
+            parent_start_col: self.parent_start_col + col,
+            _parent: PhantomData,
+            ..*self
+        }
+    }
+
+    /// Iterate over the rows of this view, each as a mutable slice
+    /// `child_dims.columns` long, stepping by the parent stride between rows.
+    /// Yields nothing for an empty view.
+    /// Panics if the view is wider than the parent stride,
+    /// since adjacent rows would then overlap in the parent,
+    /// and for the channel views of `per_channel_mut`, whose rows are not contiguous:
+    /// `pixel_rows_mut` works for those.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let columns = self.child_dims.columns;
+        assert!(self.pixel_stride == 1, "rows of a view with a pixel stride are not contiguous");
+        self.assert_rows_disjoint();
+        let rows = if self.is_empty() { 0 } else { self.child_dims.rows };
+        let view = &*self;
+        (0..rows).map(move |row| {
+            let start = view.local_index_to_parent_index(row * columns);
+            assert!(start + columns <= view.parent_len, "view extends beyond parent slice");
+            // Rows are in bounds of the parent, and cannot overlap one another
+            // since the view is no wider than the stride.
+            unsafe { core::slice::from_raw_parts_mut(view.ptr.add(start), columns) }
+        })
+    }
+
+    /// Iterate over the rows of this view, each as a mutable iterator over its pixels.
+    /// Unlike `rows_mut`, this works for every view, including the channel views
+    /// of `per_channel_mut`. Yields nothing for an empty view.
+    /// Panics if the rows of the view span more than the parent stride,
+    /// since adjacent rows would then overlap in the parent.
+    pub fn pixel_rows_mut(&mut self) -> impl Iterator<Item = IterMut<'_, T>> {
+        self.assert_rows_disjoint();
+        let columns = self.child_dims.columns;
+        let rows = if self.is_empty() { 0 } else { self.child_dims.rows };
+        let view = &*self;
+        (0..rows).map(move |row| {
+            // Rows are disjoint from one another, and the view is borrowed
+            // mutably for as long as any row is alive.
+            let view = unsafe { view.alias_window(row, 0, ImageDimensions::new(columns, 1)) };
+            IterMut { view, front: 0, back: columns }
+        })
+    }
+
+    /// Iterate mutably over the pixels of the view in row-major order.
+    /// Panics if the rows of the view span more than the parent stride,
+    /// since adjacent rows would then overlap in the parent.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.assert_rows_disjoint();
+        let back = self.len();
+        // the iterator has exclusive use of this view's window while it is alive
+        let view = unsafe { self.alias_window(0, 0, self.child_dims) };
+        IterMut { view, front: 0, back }
+    }
+
+    /// Split the view into non-overlapping tiles of `tile_dims`, in row-major order.
+    /// Tiles along the right and bottom edges are clipped to fit within the view.
+    /// Panics if the view is wider than the parent stride, since its rows
+    /// (and so its tiles) would then overlap in the parent.
+    pub fn tiles_mut(&mut self, tile_dims: ImageDimensions) -> impl Iterator<Item = SliceViewMut<'_, T>> {
+        self.assert_rows_disjoint();
+        let view = &*self;
+        tile_grid(self.child_dims, tile_dims).map(move |(row, col, dims)| {
+            // Tiles lie within the view and are disjoint from one another,
+            // and the view is borrowed mutably for as long as any tile is alive.
+            unsafe { view.alias_window(row, col, dims) }
+        })
+    }
+
+    /// Call `f` with a mutable view of each channel in turn of this view of interleaved
+    /// data, such as RGB: `channels` consecutive elements make up each pixel, so that
+    /// the view of channel `k` holds elements `k`, `k + channels`, ... of each row.
+    /// All of the operations of `SliceViewMut` work on the channel views, except
+    /// `rows_mut`, since their rows are not contiguous; use `pixel_rows_mut` instead.
+    /// Panics unless the view's width is a multiple of `channels`.
+    pub fn per_channel_mut<F: FnMut(&mut SliceViewMut<T>)>(&mut self, channels: usize, mut f: F) {
+        assert!(channels > 0, "channel count must be nonzero");
+        assert!(self.child_dims.columns % channels == 0, "view width is not a whole number of pixels");
+        self.assert_rows_disjoint();
+        let dims = ImageDimensions::new(self.child_dims.columns / channels, self.child_dims.rows);
+        let origin = if self.is_empty() { 0 } else { self.local_index_to_parent_index(0) };
+        for channel in 0..channels {
+            let offset = (origin + channel * self.pixel_stride).min(self.parent_len);
+            // Each channel view only accesses pixels of this view, which stays borrowed
+            // mutably while it is alive; the parent length is reduced to match the offset.
+            let mut view = Self {
+                parent_dims: dims,
+                child_dims: dims,
+                parent_start_col: 0,
+                parent_start_row: 0,
+                pixel_stride: self.pixel_stride * channels,
+                ptr: unsafe { self.ptr.add(offset) },
+                parent_len: self.parent_len - offset,
+                ..*self
+            };
+            f(&mut view);
+        }
+    }
+}
+
+/// Iterates mutably over the pixels of a `SliceViewMut` in row-major order
+pub struct IterMut<'b, T> {
+    view: SliceViewMut<'b, T>,
+    front: usize,
+    back: usize,
+}
+
+impl<'b, T> Iterator for IterMut<'b, T> {
+    type Item = &'b mut T;
+
+    fn next(&mut self) -> Option<&'b mut T> {
+        if self.front < self.back {
+            let px = self.view.pixel_ptr(self.front);
+            self.front += 1;
+            // each pixel is yielded only once, and no two pixels share an element
+            Some(unsafe { &mut *px })
+        }
+        else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'b, T> DoubleEndedIterator for IterMut<'b, T> {
+    fn next_back(&mut self) -> Option<&'b mut T> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(unsafe { &mut *self.view.pixel_ptr(self.back) })
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl<'b, T> ExactSizeIterator for IterMut<'b, T> {}
+
+impl<'a, 'b, T> IntoIterator for &'b mut SliceViewMut<'a, T> {
+    type Item = &'b mut T;
+    type IntoIter = IterMut<'b, T>;
+
+    fn into_iter(self) -> IterMut<'b, T> {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T> Index<usize> for SliceViewMut<'a, T> {
+    type Output = T;
+
+    /// Unlike `SliceView`, panics if `idx` is beyond the end of the view
+    fn index(&self, idx: usize) -> &T {
+        unsafe { &*self.pixel_ptr(idx) }
+    }
+}
+
+impl<'a, T> IndexMut<usize> for SliceViewMut<'a, T> {
+    fn index_mut(&mut self, idx: usize) -> &mut T {
+        unsafe { &mut *self.pixel_ptr(idx) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+    use crate::SliceView;
+
+    #[test]
+    fn rows_mut_writes_only_view() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let child = ImageDimensions::new(3,2);
+        let mut frame = FRAME_64;
+        let mut view = SliceViewMut::new(parent, 1, 2, &mut frame, child);
+        for (row_idx, row) in view.rows_mut().enumerate() {
+            assert_eq!(row.len(), 3);
+            for px in row.iter_mut() {
+                *px = row_idx as u8;
+            }
+        }
+        assert_eq!(view[0], 0);
+        assert_eq!(view[5], 1);
+
+        for (idx, (new, old)) in frame.iter().zip(FRAME_64.iter()).enumerate() {
+            let (row, col) = (idx / FRAME_64_DIM, idx % FRAME_64_DIM);
+            if (1..3).contains(&row) && (2..5).contains(&col) {
+                assert_eq!(*new, (row - 1) as u8);
+            }
+            else {
+                assert_eq!(new, old);
+            }
+        }
+    }
+
+    #[test]
+    fn invert_one_channel() {
+        // 3x2 window of a 4x3 image, each pixel two interleaved channels
+        let mut frame: [u8; 24] = core::array::from_fn(|idx| idx as u8);
+        let parent = ImageDimensions::new(8,3);
+        let mut view = SliceViewMut::new(parent, 1, 2, &mut frame, ImageDimensions::new(6,2));
+        let mut seen = Vec::new();
+        view.per_channel_mut(2, |channel| {
+            assert_eq!(channel.child_dims, ImageDimensions::new(3,2));
+            seen.push(channel[0]);
+            if seen.len() == 2 {
+                for px in channel.iter_mut() {
+                    *px = !*px;
+                }
+            }
+        });
+        assert_eq!(seen, [10, 11]);
+
+        for (idx, &px) in frame.iter().enumerate() {
+            let (row, col) = (idx / 8, idx % 8);
+            let in_window = (1..3).contains(&row) && (2..8).contains(&col);
+            if in_window && col % 2 == 1 {
+                assert_eq!(px, !(idx as u8), "at ({}, {})", row, col);
+            }
+            else {
+                assert_eq!(px, idx as u8, "at ({}, {})", row, col);
+            }
+        }
+    }
+
+    #[cfg(feature = "debug-checks")]
+    #[test]
+    #[should_panic(expected = "view window does not fit within parent")]
+    fn debug_check_overwrap() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let mut frame = FRAME_64;
+        let _ = SliceViewMut::new(parent, 0, 7, &mut frame, ImageDimensions::new(3,3));
+    }
+
+    #[test]
+    fn rows_mut_with_stride() {
+        // 4x3 image with each row padded out to 6 elements
+        const STRIDE: usize = 6;
+        let mut padded = [0xFFu8; STRIDE * 3];
+        let parent = ImageDimensions::new(4,3);
+        let child = ImageDimensions::new(2,2);
+        let mut view = SliceViewMut::new_with_stride(parent, STRIDE, 1, 1, &mut padded, child);
+        for row in view.rows_mut() {
+            row.copy_from_slice(&[1, 2]);
+        }
+        assert_eq!(padded[STRIDE + 1..STRIDE + 3], [1, 2]);
+        assert_eq!(padded[2 * STRIDE + 1..2 * STRIDE + 3], [1, 2]);
+        assert_eq!(padded.iter().filter(|&&px| px != 0xFF).count(), 4);
+
+        let view = SliceView::new_with_stride(parent, STRIDE, 1, 1, &padded, child);
+        assert_eq!(view[2], 1);
+        assert_eq!(view[3], 2);
+    }
+
+    #[test]
+    fn tiles_mut_fill() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let mut frame = FRAME_64;
+        let mut view = SliceViewMut::new(parent, 1, 1, &mut frame, ImageDimensions::new(6,6));
+        // all tiles are alive at once
+        let mut tiles: Vec<_> = view.tiles_mut(ImageDimensions::new(4,4)).collect();
+        assert_eq!(tiles.len(), 4);
+        assert_eq!(tiles[1].child_dims, ImageDimensions::new(2,4));
+        assert_eq!(tiles[3].child_dims, ImageDimensions::new(2,2));
+        for (tile_idx, tile) in tiles.iter_mut().enumerate() {
+            for idx in 0..tile.len() {
+                tile[idx] = tile_idx as u8;
+            }
+        }
+
+        for (idx, (new, old)) in frame.iter().zip(FRAME_64.iter()).enumerate() {
+            let (row, col) = (idx / FRAME_64_DIM, idx % FRAME_64_DIM);
+            let expected = match (row, col) {
+                (1..=4, 1..=4) => 0,
+                (1..=4, 5..=6) => 1,
+                (5..=6, 1..=4) => 2,
+                (5..=6, 5..=6) => 3,
+                _ => *old,
+            };
+            assert_eq!(*new, expected, "at ({}, {})", row, col);
+        }
+    }
+
+    #[test]
+    fn for_loop_zeroes_view() {
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let mut frame = FRAME_64;
+        let mut view = SliceViewMut::new(parent, 6, 6, &mut frame, ImageDimensions::new(2,2));
+        assert_eq!(view.iter_mut().len(), 4);
+        for px in &mut view {
+            *px = 0;
+        }
+        let zeroed: Vec<usize> = (0..frame.len()).filter(|&i| frame[i] == 0).collect();
+        assert_eq!(zeroed, [54, 55, 62, 63]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_beyond_view() {
+        let mut frame = FRAME_64;
+        let parent = ImageDimensions::new(FRAME_64_DIM,FRAME_64_DIM);
+        let mut view = SliceViewMut::new(parent, 1, 1, &mut frame, ImageDimensions::new(2,2));
+        view[4] = 0;
+    }
+}
@@ -0,0 +1,95 @@
+/*
+Copyright (c) 2020 Todd Stellanova
+LICENSE: BSD3 (see LICENSE file)
+*/
+//! Views of memory-mapped buffers, such as framebuffers, that must be read volatile
+
+use crate::{translate, ImageDimensions};
+
+/// A view of a portion of an image in memory that the compiler must not assume
+/// is unchanged between reads, such as a memory-mapped framebuffer.
+/// Every read is a volatile read, so none are elided, merged or reordered with one another.
+pub struct VolatileSliceView<T> {
+    pub parent_dims: ImageDimensions,
+    pub child_dims: ImageDimensions,
+    parent_start_col: usize,
+    parent_start_row: usize,
+    /// Number of elements between the starts of adjacent parent rows
+    parent_stride: usize,
+    /// Start of the parent buffer
+    ptr: *const T,
+    /// Number of elements in the parent buffer
+    parent_len: usize,
+}
+
+impl<T: Copy> VolatileSliceView<T> {
+    /// View the `child_dims` window at `(parent_start_row, parent_start_col)`
+    /// of the parent image in the `parent_len` elements at `ptr`.
+    ///
+    /// # Safety
+    /// `ptr` must be aligned for `T`, and valid for reads of
+    /// `parent_len` elements for as long as the view is used.
+    pub unsafe fn new(parent_dims: ImageDimensions, parent_stride: usize, parent_start_row: usize, parent_start_col: usize, ptr: *const T, parent_len: usize, child_dims: ImageDimensions) -> Self {
+        #[cfg(feature = "debug-checks")]
+        debug_assert!(
+            crate::check_window(parent_dims, parent_stride, parent_start_row, parent_start_col, child_dims, parent_len).is_ok(),
+            "view window does not fit within parent"
+        );
+        Self {
+            parent_dims,
+            child_dims,
+            parent_start_col,
+            parent_start_row,
+            parent_stride,
+            ptr,
+            parent_len,
+        }
+    }
+
+    /// Number of pixels in the view
+    pub fn len(&self) -> usize {
+        self.child_dims.columns * self.child_dims.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Translate a local index within this view into an index in the parent buffer.
+    /// Panics if the view is empty, having no pixels to translate.
+    pub fn local_index_to_parent_index(&self, idx: usize) -> usize {
+        assert!(!self.is_empty(), "index {} into an empty view", idx);
+        translate(idx, self.child_dims.columns, self.parent_start_row, self.parent_start_col, self.parent_stride)
+    }
+
+    /// Volatile read of the pixel at local index `idx`.
+    /// Panics if `idx` is beyond the end of the view or the parent buffer.
+    pub fn read_volatile(&self, idx: usize) -> T {
+        assert!(idx < self.len(), "index {} outside view of {} pixels", idx, self.len());
+        let parent_idx = self.local_index_to_parent_index(idx);
+        assert!(parent_idx < self.parent_len, "view extends beyond parent buffer");
+        // in bounds of the buffer, which `new`'s caller guarantees is readable
+        unsafe { self.ptr.add(parent_idx).read_volatile() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VolatileSliceView;
+    use crate::tests::{FRAME_64, FRAME_64_DIM};
+    use crate::{ImageDimensions, SliceView};
+
+    #[test]
+    fn reads_match_slice_view() {
+        let parent = ImageDimensions::new(FRAME_64_DIM, FRAME_64_DIM);
+        let child = ImageDimensions::new(3, 2);
+        let view = SliceView::new(parent, 1, 2, &FRAME_64, child);
+        let volatile = unsafe {
+            VolatileSliceView::new(parent, FRAME_64_DIM, 1, 2, FRAME_64.as_ptr(), FRAME_64.len(), child)
+        };
+        assert_eq!(volatile.len(), view.len());
+        for idx in 0..view.len() {
+            assert_eq!(volatile.read_volatile(idx), view[idx]);
+        }
+    }
+}